@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use aoc_2021_rust::error::AocError;
+use aoc_2021_rust::solver::{DayReport, Parts, Puzzle};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct BenchOptions {
+    pub(crate) iterations: usize,
+    pub(crate) warmup: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 10,
+            warmup: 1,
+        }
+    }
+}
+
+/// Min, median and max over a set of timing samples.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Stats {
+    pub(crate) min: Duration,
+    pub(crate) median: Duration,
+    pub(crate) max: Duration,
+}
+
+impl Stats {
+    pub(crate) fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let num_samples = sorted.len();
+        let median = if num_samples.is_multiple_of(2) {
+            (sorted[num_samples / 2 - 1] + sorted[num_samples / 2]) / 2
+        } else {
+            sorted[num_samples / 2]
+        };
+        Self {
+            min: sorted[0],
+            median,
+            max: sorted[num_samples - 1],
+        }
+    }
+}
+
+/// Timing statistics for every stage of one day.
+#[derive(Debug, Clone)]
+pub(crate) struct DayBench {
+    pub(crate) day: usize,
+    pub(crate) stages: Vec<(String, Stats)>,
+}
+
+impl DayBench {
+    fn total_median(&self) -> Duration {
+        self.stages.iter().map(|(_, stats)| stats.median).sum()
+    }
+}
+
+/// Run `puzzle` `warmup` times untimed and then `iterations` times, returning
+/// the last report, with its timings replaced by the medians, alongside
+/// per-stage statistics.
+pub(crate) fn bench_day(
+    puzzle: &dyn Puzzle,
+    raw: &str,
+    parts: Parts,
+    options: BenchOptions,
+) -> Result<(DayReport, DayBench), AocError> {
+    for _ in 0..options.warmup {
+        puzzle.run(raw, parts)?;
+    }
+    let mut reports = Vec::with_capacity(options.iterations);
+    for _ in 0..options.iterations {
+        reports.push(puzzle.run(raw, parts)?);
+    }
+    let parse_samples = reports.iter().map(|r| r.parse_time).collect::<Vec<_>>();
+    let parse_stats = Stats::from_samples(&parse_samples);
+    let mut stages = vec![(String::from("parse"), parse_stats)];
+    let mut last = reports.pop().expect("At least one iteration");
+    last.parse_time = parse_stats.median;
+    for (pidx, part) in last.parts.iter_mut().enumerate() {
+        let mut samples = reports
+            .iter()
+            .map(|r| r.parts[pidx].elapsed)
+            .collect::<Vec<_>>();
+        samples.push(part.elapsed);
+        let stats = Stats::from_samples(&samples);
+        part.elapsed = stats.median;
+        stages.push((format!("part {}", part.part), stats));
+    }
+    let bench = DayBench {
+        day: last.day,
+        stages,
+    };
+    Ok((last, bench))
+}
+
+fn print_row(label: &str, stats: &Stats) {
+    println!(
+        "{:<12} {:>12} {:>12} {:>12}",
+        label,
+        format!("{:.3?}", stats.min),
+        format!("{:.3?}", stats.median),
+        format!("{:.3?}", stats.max)
+    );
+}
+
+fn print_header(first_column: &str) {
+    println!(
+        "{:<12} {:>12} {:>12} {:>12}",
+        first_column, "min", "median", "max"
+    );
+}
+
+pub(crate) fn print_day(bench: &DayBench, options: BenchOptions) {
+    println!(
+        "\nTimings over {} iterations ({} warm-up)",
+        options.iterations, options.warmup
+    );
+    print_header("stage");
+    for (label, stats) in bench.stages.iter() {
+        print_row(label, stats);
+    }
+}
+
+pub(crate) fn print_summary(benches: &[DayBench]) {
+    println!("\nSummary (median per stage)");
+    println!(
+        "{:<6} {:>12} {:>12} {:>12} {:>12}",
+        "day", "parse", "part 1", "part 2", "total"
+    );
+    let stage_median = |bench: &DayBench, label: &str| {
+        bench
+            .stages
+            .iter()
+            .find(|(l, _)| l == label)
+            .map_or(String::from("-"), |(_, s)| format!("{:.3?}", s.median))
+    };
+    for bench in benches {
+        println!(
+            "{:<6} {:>12} {:>12} {:>12} {:>12}",
+            bench.day,
+            stage_median(bench, "parse"),
+            stage_median(bench, "part 1"),
+            stage_median(bench, "part 2"),
+            format!("{:.3?}", bench.total_median())
+        );
+    }
+    let total: Duration = benches.iter().map(DayBench::total_median).sum();
+    println!(
+        "{:<6} {:>12} {:>12} {:>12} {:>12}",
+        "all",
+        "",
+        "",
+        "",
+        format!("{:.3?}", total)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::from_samples(&[ms(5), ms(1), ms(3)]);
+        assert_eq!((ms(1), ms(3), ms(5)), (stats.min, stats.median, stats.max));
+        let stats = Stats::from_samples(&[ms(4), ms(1), ms(2), ms(8)]);
+        assert_eq!((ms(1), ms(3), ms(8)), (stats.min, stats.median, stats.max));
+    }
+}
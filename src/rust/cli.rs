@@ -0,0 +1,382 @@
+use std::error::Error;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use aoc_2021_rust::error::AocError;
+use aoc_2021_rust::solver::Parts;
+
+use crate::bench::BenchOptions;
+use crate::output::OutputFormat;
+use crate::verify::DEFAULT_ANSWERS_FILE;
+
+pub(crate) static USAGE: &str = "Usage: aoc-2021-rust [DAYS...] [OPTIONS]
+
+Arguments:
+  DAYS                 Days to run: a single day (`5`), an inclusive range (`1-4`),
+                       a comma-separated list (`1,3,5`) or `all` (the default)
+
+Options:
+  -p, --part N         Only run part N of each selected day
+  -i, --input PATH     Read the input of the (single) selected day from PATH,
+                       or from stdin if PATH is `-`
+  -d, --input-dir DIR  Look for each day's input file in DIR; defaults to
+                       $AOC_INPUT_DIR, or `input` if that is unset
+  -j, --jobs N         Run up to N days at once on separate threads; results
+                       are still reported in day order [default: 1]
+  -b, --bench          Time parsing and each part, reporting min/median/max
+  -n, --iterations N   Number of timed runs per day in --bench mode [default: 10]
+      --warmup N       Number of untimed runs per day in --bench mode [default: 1]
+  -o, --output FORMAT  Report answers as `text`, `json` or `csv` [default: text]
+      --verify         Compare answers against the known-good ones, failing on
+                       any mismatch
+      --answers PATH   Known-good answers for --verify [default: answers.toml]
+  -h, --help           Print this help";
+
+pub(crate) static INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+static DEFAULT_INPUT_DIR: &str = "input";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+    /// Name to report in parse errors.
+    pub(crate) fn path(&self) -> &Path {
+        match self {
+            InputSource::Stdin => Path::new("<stdin>"),
+            InputSource::File(path) => path,
+        }
+    }
+    pub(crate) fn read(&self) -> Result<String, AocError> {
+        match self {
+            InputSource::Stdin => std::io::read_to_string(std::io::stdin())
+                .map_err(|source| AocError::Io { path: None, source }),
+            InputSource::File(path) => {
+                std::fs::read_to_string(path).map_err(|source| AocError::Io {
+                    path: Some(path.clone()),
+                    source,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct Options {
+    pub(crate) days: Vec<usize>,
+    pub(crate) parts: Parts,
+    pub(crate) input: Option<InputSource>,
+    pub(crate) input_dir: Option<PathBuf>,
+    /// Number of days to run concurrently, if more than one.
+    pub(crate) jobs: Option<usize>,
+    pub(crate) bench: Option<BenchOptions>,
+    pub(crate) output: OutputFormat,
+    /// Answers file to check results against in --verify mode.
+    pub(crate) verify: Option<PathBuf>,
+}
+
+impl Options {
+    /// Where to read the input for a day whose default file name is
+    /// `input_file`; `env_input_dir` is the value of `AOC_INPUT_DIR`.
+    pub(crate) fn input_source(
+        &self,
+        input_file: &str,
+        env_input_dir: Option<OsString>,
+    ) -> InputSource {
+        if let Some(input) = &self.input {
+            return input.clone();
+        }
+        let dir = match (&self.input_dir, env_input_dir) {
+            (Some(dir), _) => dir.clone(),
+            (None, Some(dir)) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(DEFAULT_INPUT_DIR),
+        };
+        InputSource::File(dir.join(Path::new(input_file)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Command {
+    Run(Options),
+    Help,
+}
+
+fn parse_day(raw: &str, num_days: usize) -> Result<usize, Box<dyn Error>> {
+    let day = raw
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| format!("Failed to parse {:?} as a day", raw))?;
+    if day == 0 || day > num_days {
+        return Err(format!("Unknown day {}; available days are 1-{}", day, num_days).into());
+    }
+    Ok(day)
+}
+
+fn parse_days(spec: &str, num_days: usize, days: &mut Vec<usize>) -> Result<(), Box<dyn Error>> {
+    for item in spec.split(',').filter(|s| !s.is_empty()) {
+        if item == "all" {
+            days.extend(1..=num_days);
+        } else if let Some((start, stop)) = item.split_once('-') {
+            let (start, stop) = (parse_day(start, num_days)?, parse_day(stop, num_days)?);
+            if start > stop {
+                return Err(format!("Day range {} is empty", item).into());
+            }
+            days.extend(start..=stop);
+        } else {
+            days.push(parse_day(item, num_days)?);
+        }
+    }
+    Ok(())
+}
+
+fn parse_count(raw: &str, name: &str, min: usize) -> Result<usize, Box<dyn Error>> {
+    match raw.parse::<usize>() {
+        Ok(count) if count >= min => Ok(count),
+        _ => Err(format!(
+            "Expected an integer of at least {} for {}, found {:?}",
+            min, name, raw
+        )
+        .into()),
+    }
+}
+
+fn parse_part(raw: &str) -> Result<Parts, Box<dyn Error>> {
+    match raw {
+        "1" => Ok(Parts::One),
+        "2" => Ok(Parts::Two),
+        _ => Err(format!("Failed to parse {:?} as a part; expected 1 or 2", raw).into()),
+    }
+}
+
+pub(crate) fn parse_args<I>(args: I, num_days: usize) -> Result<Command, Box<dyn Error>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut days = Vec::new();
+    let mut parts = Parts::Both;
+    let mut input = None;
+    let mut input_dir = None;
+    let mut jobs = 1;
+    let mut bench = false;
+    let mut bench_options = BenchOptions::default();
+    let mut bench_tuned = false;
+    let mut output = OutputFormat::default();
+    let mut verify = false;
+    let mut answers = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name, Some(String::from(value))),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("Missing value for {}", name))
+        };
+        match name {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-p" | "--part" => parts = parse_part(&value()?)?,
+            "-i" | "--input" => input = Some(InputSource::from_arg(&value()?)),
+            "-d" | "--input-dir" => input_dir = Some(PathBuf::from(value()?)),
+            "-j" | "--jobs" => jobs = parse_count(&value()?, name, 1)?,
+            "-b" | "--bench" => bench = true,
+            "-n" | "--iterations" => {
+                bench_options.iterations = parse_count(&value()?, name, 1)?;
+                bench_tuned = true;
+            }
+            "-o" | "--output" => output = OutputFormat::from_arg(&value()?)?,
+            "--verify" => verify = true,
+            "--answers" => answers = Some(PathBuf::from(value()?)),
+            "--warmup" => {
+                bench_options.warmup = parse_count(&value()?, name, 0)?;
+                bench_tuned = true;
+            }
+            "-" => return Err(String::from("Use --input - to read from stdin").into()),
+            _ if name.starts_with('-') => return Err(format!("Unknown option {}", name).into()),
+            _ => parse_days(&arg, num_days, &mut days)?,
+        }
+    }
+    if days.is_empty() {
+        days.extend(1..=num_days);
+    }
+    days.sort_unstable();
+    days.dedup();
+    if input.is_some() && days.len() != 1 {
+        return Err(String::from("--input requires exactly one day to be selected").into());
+    }
+    if bench_tuned && !bench {
+        return Err(String::from("--iterations and --warmup require --bench").into());
+    }
+    if bench && jobs > 1 {
+        return Err(String::from("--jobs cannot be combined with --bench").into());
+    }
+    if answers.is_some() && !verify {
+        return Err(String::from("--answers requires --verify").into());
+    }
+    Ok(Command::Run(Options {
+        days,
+        parts,
+        input,
+        input_dir,
+        jobs: (jobs > 1).then_some(jobs),
+        bench: bench.then_some(bench_options),
+        output,
+        verify: verify.then(|| answers.unwrap_or_else(|| PathBuf::from(DEFAULT_ANSWERS_FILE))),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, Box<dyn Error>> {
+        parse_args(args.iter().map(|&s| String::from(s)), 7)
+    }
+
+    #[test]
+    fn test_default_runs_everything() {
+        let expected = Options {
+            days: vec![1, 2, 3, 4, 5, 6, 7],
+            ..Options::default()
+        };
+        assert_eq!(Command::Run(expected.clone()), parse(&[]).unwrap());
+        assert_eq!(Command::Run(expected), parse(&["all"]).unwrap());
+    }
+
+    #[test]
+    fn test_day_and_part_selection() {
+        let expected = Options {
+            days: vec![1, 2, 3, 4, 6],
+            parts: Parts::Two,
+            ..Options::default()
+        };
+        let command = parse(&["6", "1-4", "--part", "2", "3"]).unwrap();
+        assert_eq!(Command::Run(expected), command);
+        let expected = Options {
+            days: vec![1, 3, 5],
+            parts: Parts::One,
+            ..Options::default()
+        };
+        assert_eq!(
+            Command::Run(expected),
+            parse(&["1,3,5", "-p", "1"]).unwrap()
+        );
+        let expected = Options {
+            days: vec![1, 2, 3, 4, 5, 6, 7],
+            jobs: Some(4),
+            ..Options::default()
+        };
+        assert_eq!(Command::Run(expected), parse(&["-j", "4"]).unwrap());
+        let command = parse(&["--jobs=1"]).unwrap();
+        assert_eq!(parse(&[]).unwrap(), command);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(parse(&["8"]).is_err());
+        assert!(parse(&["0"]).is_err());
+        assert!(parse(&["4-2"]).is_err());
+        assert!(parse(&["five"]).is_err());
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--part"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+        assert!(parse(&["1-2", "--input", "puzz1.csv"]).is_err());
+        assert!(parse(&["5", "--input"]).is_err());
+        assert!(parse(&["--bench", "-n", "0"]).is_err());
+        assert!(parse(&["--iterations", "5"]).is_err());
+        assert!(parse(&["--output", "xml"]).is_err());
+        assert!(parse(&["--answers", "answers.toml"]).is_err());
+        assert!(parse(&["--jobs", "0"]).is_err());
+        assert!(parse(&["--jobs", "4", "--bench"]).is_err());
+    }
+
+    #[test]
+    fn test_bench_options() {
+        let expected = Options {
+            days: vec![6],
+            bench: Some(BenchOptions {
+                iterations: 25,
+                warmup: 0,
+            }),
+            ..Options::default()
+        };
+        let command = parse(&["6", "--bench", "--iterations=25", "--warmup", "0"]).unwrap();
+        assert_eq!(Command::Run(expected), command);
+        let expected = Options {
+            days: vec![6],
+            bench: Some(BenchOptions::default()),
+            output: OutputFormat::Json,
+            ..Options::default()
+        };
+        assert_eq!(
+            Command::Run(expected),
+            parse(&["6", "-b", "-o", "json"]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_verify_options() {
+        let expected = Options {
+            days: vec![4],
+            verify: Some(PathBuf::from("answers.toml")),
+            ..Options::default()
+        };
+        assert_eq!(Command::Run(expected), parse(&["4", "--verify"]).unwrap());
+        let expected = Options {
+            days: vec![1, 2, 3, 4, 5, 6, 7],
+            verify: Some(PathBuf::from("alice/answers.toml")),
+            ..Options::default()
+        };
+        let command = parse(&["--answers=alice/answers.toml", "--verify"]).unwrap();
+        assert_eq!(Command::Run(expected), command);
+    }
+
+    #[test]
+    fn test_input_sources() {
+        let options = match parse(&["5", "--input", "-"]).unwrap() {
+            Command::Run(options) => options,
+            Command::Help => panic!("Expected options"),
+        };
+        assert_eq!(InputSource::Stdin, options.input_source("puzz5.txt", None));
+        let options = match parse(&["5", "--input=alice/day5.txt"]).unwrap() {
+            Command::Run(options) => options,
+            Command::Help => panic!("Expected options"),
+        };
+        assert_eq!(
+            InputSource::File(PathBuf::from("alice/day5.txt")),
+            options.input_source("puzz5.txt", Some(OsString::from("bob")))
+        );
+
+        let options = match parse(&["5", "-d", "alice"]).unwrap() {
+            Command::Run(options) => options,
+            Command::Help => panic!("Expected options"),
+        };
+        assert_eq!(
+            InputSource::File(Path::new("alice").join("puzz5.txt")),
+            options.input_source("puzz5.txt", Some(OsString::from("bob")))
+        );
+        let options = match parse(&["5"]).unwrap() {
+            Command::Run(options) => options,
+            Command::Help => panic!("Expected options"),
+        };
+        assert_eq!(
+            InputSource::File(Path::new("bob").join("puzz5.txt")),
+            options.input_source("puzz5.txt", Some(OsString::from("bob")))
+        );
+        assert_eq!(
+            InputSource::File(Path::new("input").join("puzz5.txt")),
+            options.input_source("puzz5.txt", None)
+        );
+    }
+}
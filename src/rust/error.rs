@@ -0,0 +1,160 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Everything that can go wrong while reading and parsing puzzle input.
+#[derive(Debug)]
+pub enum AocError {
+    /// The input could not be read; `path` is `None` for stdin.
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// A malformed token at a 1-based line and column of the input.
+    Parse {
+        file: Option<PathBuf>,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// Input that is well-formed but cannot be solved, e.g. an empty list.
+    InvalidData(String),
+}
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            file: None,
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+    pub fn invalid(message: impl Into<String>) -> Self {
+        AocError::InvalidData(message.into())
+    }
+    /// Move a parse error found in a single line to line `line` of the input.
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            AocError::Parse {
+                file,
+                column,
+                message,
+                ..
+            } => AocError::Parse {
+                file,
+                line,
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+    /// Record which file a parse error came from.
+    pub fn in_file(self, path: &Path) -> Self {
+        match self {
+            AocError::Parse {
+                line,
+                column,
+                message,
+                ..
+            } => AocError::Parse {
+                file: Some(path.to_path_buf()),
+                line,
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+    /// The 1-based line and column of a parse error.
+    pub fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            AocError::Parse { line, column, .. } => Some((*line, *column)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "Could not read {}: {}", path.display(), source),
+            AocError::Io { path: None, source } => write!(f, "Could not read stdin: {}", source),
+            AocError::Parse {
+                file,
+                line,
+                column,
+                message,
+            } => {
+                match file {
+                    Some(file) => write!(f, "{}:", file.display())?,
+                    None => write!(f, "input:")?,
+                }
+                write!(f, "{}:{}: {}", line, column, message)
+            }
+            AocError::InvalidData(message) => write!(f, "Invalid puzzle data: {}", message),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// 1-based column at which `token`, a sub-slice of `line`, starts.
+pub fn column_of(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if offset <= line.len() {
+        offset + 1
+    } else {
+        1
+    }
+}
+
+/// Parse `token`, a sub-slice of the 1-based `line_no`-th input line `line`,
+/// reporting where it sits in the input on failure.
+pub(crate) fn parse_token<T>(token: &str, line: &str, line_no: usize) -> Result<T, AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token.trim().parse::<T>().map_err(|e| {
+        AocError::parse(
+            line_no,
+            column_of(line, token),
+            format!("Failed to parse {:?}: {}", token, e),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token_location() {
+        let line = "12,x4,7";
+        let token = line.split(',').nth(1).unwrap();
+        let err = parse_token::<u32>(token, line, 3).unwrap_err();
+        match err.in_file(Path::new("input/puzz6.txt")) {
+            AocError::Parse {
+                file, line, column, ..
+            } => {
+                assert_eq!(Some(PathBuf::from("input/puzz6.txt")), file);
+                assert_eq!((3, 4), (line, column));
+            }
+            other => panic!("Unexpected error {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,394 @@
+//! Line-oriented helpers shared by every day's parser.
+//!
+//! Line endings are normalised (`\n` and `\r\n` are both accepted), trailing
+//! whitespace is ignored on every line and trailing blank lines are dropped.
+//! Errors report the 1-based line and column of the offending token in the
+//! raw input.
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::error::{column_of, parse_token, AocError};
+
+/// One line of input, without its line ending or trailing whitespace.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// 1-based line number within the input.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+    /// An error pointing at `token`, a sub-slice of this line.
+    pub fn error(&self, token: &str, message: impl Into<String>) -> AocError {
+        AocError::parse(self.number, column_of(self.text, token), message)
+    }
+    /// Parse `token`, a sub-slice of this line.
+    pub fn parse_token<T>(&self, token: &str) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        parse_token(token, self.text, self.number)
+    }
+    /// Parse the whole line as a single value.
+    pub fn parse<T>(&self) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.parse_token(self.text)
+    }
+    /// Parse the line as a list of values separated by `separator`.
+    pub fn list<T>(&self, separator: char) -> Result<Vec<T>, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .split(separator)
+            .map(|token| self.parse_token(token))
+            .collect()
+    }
+    /// Parse the line as a list of comma-separated values.
+    pub fn comma_list<T>(&self) -> Result<Vec<T>, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.list(',')
+    }
+    /// Parse the line as a list of values separated by runs of whitespace.
+    pub fn whitespace_list<T>(&self) -> Result<Vec<T>, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .split_whitespace()
+            .map(|token| self.parse_token(token))
+            .collect()
+    }
+    /// Parse `token`, a sub-slice of this line, as an inclusive
+    /// `axis=min..max` range.
+    pub fn range<T>(&self, token: &str, axis: &str) -> Result<(T, T), AocError>
+    where
+        T: FromStr + PartialOrd,
+        T::Err: Display,
+    {
+        let range = token
+            .strip_prefix(axis)
+            .and_then(|t| t.strip_prefix('='))
+            .ok_or_else(|| self.error(token, format!("Expected `{}=min..max`", axis)))?;
+        let (min, max) = range
+            .split_once("..")
+            .ok_or_else(|| self.error(range, "Expected `min..max`"))?;
+        let (min, max) = (self.parse_token(min)?, self.parse_token(max)?);
+        if min > max {
+            return Err(self.error(range, "Range ends before it starts"));
+        }
+        Ok((min, max))
+    }
+}
+
+/// Every line of `raw`, including interior blank lines.
+pub fn lines(raw: &str) -> Vec<Line<'_>> {
+    let mut lines = raw
+        .lines()
+        .enumerate()
+        .map(|(lidx, text)| Line {
+            number: lidx + 1,
+            text: text.trim_end(),
+        })
+        .collect::<Vec<Line>>();
+    while lines.last().is_some_and(Line::is_blank) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Parse every non-blank line of `raw` with `parse`, requiring at least one.
+pub fn parse_lines<'a, T>(
+    raw: &'a str,
+    parse: impl FnMut(Line<'a>) -> Result<T, AocError>,
+) -> Result<Vec<T>, AocError> {
+    let parsed = lines(raw)
+        .into_iter()
+        .filter(|line| !line.is_blank())
+        .map(parse)
+        .collect::<Result<Vec<T>, AocError>>()?;
+    if parsed.is_empty() {
+        return Err(AocError::invalid("Empty input"));
+    }
+    Ok(parsed)
+}
+
+/// Groups of consecutive non-blank lines separated by one or more blank lines.
+pub fn blocks(raw: &str) -> Vec<Vec<Line<'_>>> {
+    let mut blocks: Vec<Vec<Line>> = Vec::new();
+    let mut current: Vec<Line> = Vec::new();
+    for line in lines(raw) {
+        if line.is_blank() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+    blocks
+}
+
+/// The single non-blank line of `raw`.
+pub fn single_line(raw: &str) -> Result<Line<'_>, AocError> {
+    let mut non_blank = lines(raw).into_iter().filter(|line| !line.is_blank());
+    let line = non_blank
+        .next()
+        .ok_or_else(|| AocError::invalid("Empty input"))?;
+    match non_blank.next() {
+        Some(extra) => Err(extra.error(extra.text, "Expected a single line of input")),
+        None => Ok(line),
+    }
+}
+
+/// Parse input consisting of one line of comma-separated values.
+pub fn comma_list<T>(raw: &str) -> Result<Vec<T>, AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    single_line(raw)?.comma_list()
+}
+
+/// A rectangular grid of cells, stored row-major.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// The cell in column `x` of row `y`, if it is inside the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+    pub fn rows(&self) -> std::slice::ChunksExact<'_, T> {
+        self.cells.chunks_exact(self.width)
+    }
+    /// Coordinates of the cells directly above, below, left and right of
+    /// `(x, y)` that are inside the grid.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        neighbours(x, y, self.width, self.height)
+    }
+    /// Coordinates of the up to eight cells surrounding `(x, y)`, including
+    /// diagonals.
+    pub fn neighbours_with_diagonals(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        offset_cells(x, y, self.width, self.height, &DIAGONAL_OFFSETS)
+    }
+}
+
+/// Offsets of the cells above, left, right and below a cell.
+const ORTHOGONAL_OFFSETS: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the eight cells surrounding a cell, in reading order.
+const DIAGONAL_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+fn offset_cells(
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    offsets: &'static [(isize, isize)],
+) -> impl Iterator<Item = (usize, usize)> {
+    offsets.iter().filter_map(move |&(dx, dy)| {
+        let nx = x.checked_add_signed(dx)?;
+        let ny = y.checked_add_signed(dy)?;
+        (nx < width && ny < height).then_some((nx, ny))
+    })
+}
+
+/// Coordinates of the cells directly above, below, left and right of
+/// `(x, y)` within a `width` by `height` area, for areas that are not stored
+/// as a [`Grid`].
+pub fn neighbours(
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    offset_cells(x, y, width, height, &ORTHOGONAL_OFFSETS)
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+    /// The cell at `(x, y)`; panics outside the grid.
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).expect("Grid index out of bounds")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "Grid index out of bounds"
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// One line per row, with no separator between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (ridx, row) in self.rows().enumerate() {
+            if ridx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse `raw` as a rectangular grid with one character per cell; `cell`
+/// returns `None` for characters that are not allowed.
+pub fn grid<T>(raw: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, AocError> {
+    grid_from_lines(&lines(raw), cell)
+}
+
+/// Like [`grid`], for rows that have already been split into lines, such as
+/// one of the [`blocks`] of a larger input.
+pub fn grid_from_lines<T>(
+    rows: &[Line],
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> Result<Grid<T>, AocError> {
+    let width = rows.first().map_or(0, |row| row.text.chars().count());
+    if width == 0 {
+        return Err(AocError::invalid("Empty grid"));
+    }
+    let mut cells = Vec::with_capacity(width * rows.len());
+    for row in rows.iter() {
+        let row_width = row.text.chars().count();
+        if row_width != width {
+            return Err(row.error(
+                row.text,
+                format!("Expected a row of {} cells, found {}", width, row_width),
+            ));
+        }
+        for (idx, c) in row.text.char_indices() {
+            let value = cell(c)
+                .ok_or_else(|| row.error(&row.text[idx..], format!("Unexpected {:?}", c)))?;
+            cells.push(value);
+        }
+    }
+    Ok(Grid {
+        width,
+        height: rows.len(),
+        cells,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_endings_and_trailing_whitespace() {
+        let raw = "1,2 \r\n\r\n3\t\r\n\n\n";
+        let texts = lines(raw).iter().map(|l| l.text).collect::<Vec<&str>>();
+        assert_eq!(vec!["1,2", "", "3"], texts);
+        assert_eq!(vec![7, 2], comma_list::<u8>("7,2\r\n").unwrap());
+        let parsed = parse_lines("4 \r\n\r\n3\r\n", |l| l.parse::<u8>()).unwrap();
+        assert_eq!(vec![4, 3], parsed);
+    }
+
+    #[test]
+    fn test_blocks() {
+        let raw = "a\n\n\nb\nc\n  \nd\n";
+        let blocks = blocks(raw)
+            .iter()
+            .map(|b| b.iter().map(|l| (l.number, l.text)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![vec![(1, "a")], vec![(4, "b"), (5, "c")], vec![(7, "d")]],
+            blocks
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = grid("123\n456\n", |c| c.to_digit(10)).unwrap();
+        assert_eq!((3, 2), (grid.width, grid.height));
+        assert_eq!(6, grid[(2, 1)]);
+        assert_eq!(None, grid.get(3, 0));
+        assert_eq!(vec![&[4, 5, 6]], grid.rows().skip(1).collect::<Vec<_>>());
+        let neighbours = grid.neighbours(0, 1).collect::<Vec<_>>();
+        assert_eq!(vec![(0, 0), (1, 1)], neighbours);
+        let neighbours = grid.neighbours_with_diagonals(0, 1).collect::<Vec<_>>();
+        assert_eq!(vec![(0, 0), (1, 0), (1, 1)], neighbours);
+        assert_eq!("123\n456", grid.to_string());
+    }
+
+    #[test]
+    fn test_located_errors() {
+        let err = comma_list::<u8>("1,2,x3\n").unwrap_err();
+        assert_eq!(Some((1, 5)), err.location());
+        let err = comma_list::<u8>("1,2\n3\n").unwrap_err();
+        assert_eq!(Some((2, 1)), err.location());
+        let err = grid("12\n3x\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(Some((2, 2)), err.location());
+        let err = grid("12\n345\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(Some((2, 1)), err.location());
+        let err = parse_lines("1\n\n 2 3", |l| l.parse::<u8>()).unwrap_err();
+        assert_eq!(Some((3, 1)), err.location());
+        assert!(matches!(
+            comma_list::<u8>("\n \n"),
+            Err(AocError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn test_range() {
+        let line = Line {
+            number: 3,
+            text: "x=-5..12, y=4..2, z=1-2",
+        };
+        let tokens = line.text.split(", ").collect::<Vec<&str>>();
+        assert_eq!((-5, 12), line.range::<i32>(tokens[0], "x").unwrap());
+        assert_eq!(
+            Some((3, 1)),
+            line.range::<i32>(tokens[0], "y").unwrap_err().location()
+        );
+        assert_eq!(
+            Some((3, 13)),
+            line.range::<i32>(tokens[1], "y").unwrap_err().location()
+        );
+        assert_eq!(
+            Some((3, 21)),
+            line.range::<i32>(tokens[2], "z").unwrap_err().location()
+        );
+    }
+}
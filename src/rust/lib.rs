@@ -0,0 +1,32 @@
+//! Advent of Code 2021 solutions.
+//!
+//! Each `puzzN` module exposes the parsing and solving functions for day N,
+//! together with a unit struct implementing [`solver::Solver`]. The
+//! [`solver::registry`] lists every implemented day for front ends such as
+//! the `aoc-2021-rust` binary.
+
+pub mod error;
+pub mod input;
+pub mod puzz1;
+pub mod puzz10;
+pub mod puzz11;
+pub mod puzz12;
+pub mod puzz13;
+pub mod puzz14;
+pub mod puzz15;
+pub mod puzz16;
+pub mod puzz17;
+pub mod puzz18;
+pub mod puzz19;
+pub mod puzz2;
+pub mod puzz20;
+pub mod puzz21;
+pub mod puzz22;
+pub mod puzz3;
+pub mod puzz4;
+pub mod puzz5;
+pub mod puzz6;
+pub mod puzz7;
+pub mod puzz8;
+pub mod puzz9;
+pub mod solver;
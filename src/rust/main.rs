@@ -1,32 +1,108 @@
-mod puzz1;
-mod puzz2;
-mod puzz3;
-mod puzz4;
-mod puzz5;
-mod puzz6;
-mod puzz7;
+mod bench;
+mod cli;
+mod output;
+mod parallel;
+mod verify;
+
+use aoc_2021_rust::solver;
+use cli::Command;
+use output::OutputFormat;
+use verify::ExpectedAnswers;
 
 fn main() {
-    println!("Hello, AoC is fun!");
-    println!("\nPuzzle 1");
-    println!("--------\n");
-    puzz1::puzz1();
-    println!("\nPuzzle 2");
-    println!("--------\n");
-    puzz2::puzz2();
-    println!("\nPuzzle 3");
-    println!("--------\n");
-    puzz3::puzz3();
-    println!("\nPuzzle 4");
-    println!("--------\n");
-    puzz4::puzz4();
-    println!("\nPuzzle 5");
-    println!("--------\n");
-    puzz5::puzz5();
-    println!("\nPuzzle 6");
-    println!("--------\n");
-    puzz6::puzz6();
-    println!("\nPuzzle 7");
-    println!("--------\n");
-    puzz7::puzz7();
+    let puzzles = solver::registry();
+    let options = match cli::parse_args(std::env::args().skip(1), puzzles.len()) {
+        Ok(Command::Run(options)) => options,
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let expected = match options
+        .verify
+        .as_deref()
+        .map(ExpectedAnswers::load)
+        .transpose()
+    {
+        Ok(expected) => expected,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let text = options.output == OutputFormat::Text;
+    if text {
+        println!("Hello, AoC is fun!");
+    }
+    let mut reports = Vec::new();
+    let mut benches = Vec::new();
+    let mut checks = Vec::new();
+    let env_input_dir = std::env::var_os(cli::INPUT_DIR_VAR);
+    let run_day = |&day: &usize| {
+        let puzzle = puzzles[day - 1].as_ref();
+        let source = options.input_source(puzzle.input_file(), env_input_dir.clone());
+        let outcome = source.read().and_then(|raw| match options.bench {
+            Some(bench_options) => bench::bench_day(puzzle, &raw, options.parts, bench_options)
+                .map(|(report, bench)| (report, Some(bench))),
+            None => puzzle.run(&raw, options.parts).map(|report| (report, None)),
+        });
+        let outcome = outcome.map_err(|e| e.in_file(source.path()));
+        (day, source, outcome)
+    };
+    let jobs = options.jobs.unwrap_or(1);
+    parallel::for_each_ordered(&options.days, jobs, run_day, |(day, source, outcome)| {
+        if text {
+            let title = format!("Puzzle {}", day);
+            println!("\n{}", title);
+            println!("{}\n", "-".repeat(title.len()));
+        }
+        match outcome {
+            Ok((report, bench)) => {
+                if text {
+                    for part in report.parts.iter() {
+                        println!("{}", part.description);
+                    }
+                }
+                if let (Some(bench), Some(bench_options)) = (bench, options.bench) {
+                    if text {
+                        bench::print_day(&bench, bench_options);
+                    }
+                    benches.push(bench);
+                }
+                if let Some(expected) = &expected {
+                    checks.extend(verify::check_report(expected, source.path(), &report));
+                }
+                reports.push(report);
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    });
+    match options.output {
+        OutputFormat::Text => {
+            if options.bench.is_some() {
+                bench::print_summary(&benches);
+            }
+        }
+        OutputFormat::Json => println!("{}", output::to_json(&reports)),
+        OutputFormat::Csv => println!("{}", output::to_csv(&reports)),
+    }
+    if expected.is_some() {
+        let (report, all_passed) = verify::render(&checks);
+        // Keep stdout machine-readable for the structured formats.
+        if text {
+            println!("\n{}", report);
+        } else {
+            eprintln!("{}", report);
+        }
+        if !all_passed {
+            std::process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,145 @@
+use std::error::Error;
+
+use aoc_2021_rust::solver::DayReport;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub(crate) enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    pub(crate) fn from_arg(arg: &str) -> Result<Self, Box<dyn Error>> {
+        match arg {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!(
+                "Failed to parse {:?} as an output format; expected text, json or csv",
+                arg
+            )
+            .into()),
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        String::from(s)
+    }
+}
+
+/// Every part of every report as a JSON array with one object per part.
+pub(crate) fn to_json(reports: &[DayReport]) -> String {
+    let mut records = Vec::new();
+    for report in reports {
+        for part in report.parts.iter() {
+            let fields = part
+                .fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}", json_string(name), json_string(value)))
+                .collect::<Vec<String>>();
+            records.push(format!(
+                "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"fields\": {{{}}}, \"parse_ns\": {}, \"elapsed_ns\": {}}}",
+                report.day,
+                part.part,
+                json_string(&part.answer),
+                fields.join(", "),
+                report.parse_time.as_nanos(),
+                part.elapsed.as_nanos()
+            ));
+        }
+    }
+    if records.is_empty() {
+        String::from("[]")
+    } else {
+        format!("[\n{}\n]", records.join(",\n"))
+    }
+}
+
+/// Every part of every report as CSV with a header row; supporting fields are
+/// packed into one `name=value;...` column.
+pub(crate) fn to_csv(reports: &[DayReport]) -> String {
+    let mut lines = vec![String::from("day,part,answer,fields,parse_ns,elapsed_ns")];
+    for report in reports {
+        for part in report.parts.iter() {
+            let fields = part
+                .fields
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<String>>()
+                .join(";");
+            lines.push(format!(
+                "{},{},{},{},{},{}",
+                report.day,
+                part.part,
+                csv_field(&part.answer),
+                csv_field(&fields),
+                report.parse_time.as_nanos(),
+                part.elapsed.as_nanos()
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_2021_rust::solver::PartReport;
+
+    use super::*;
+
+    fn reports() -> Vec<DayReport> {
+        vec![DayReport {
+            day: 4,
+            parse_time: Duration::from_nanos(1_500),
+            parts: vec![PartReport {
+                part: 1,
+                answer: String::from("4512"),
+                fields: vec![("board_idx", String::from("2"))],
+                description: String::from("Part One: Winning score: 4512, from the 2-th board"),
+                elapsed: Duration::from_nanos(2_000),
+            }],
+        }]
+    }
+
+    #[test]
+    fn test_to_json() {
+        let expected = "[
+  {\"day\": 4, \"part\": 1, \"answer\": \"4512\", \"fields\": {\"board_idx\": \"2\"}, \"parse_ns\": 1500, \"elapsed_ns\": 2000}
+]";
+        assert_eq!(expected, to_json(&reports()));
+        assert_eq!("\"a\\\"b\\n\"", json_string("a\"b\n"));
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let expected = "day,part,answer,fields,parse_ns,elapsed_ns\n4,1,4512,board_idx=2,1500,2000";
+        assert_eq!(expected, to_csv(&reports()));
+        assert_eq!("\"x,\"\"y\"\"\"", csv_field("x,\"y\""));
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Apply `work` to every item on up to `jobs` threads, handing each result to
+/// `emit` on the calling thread in item order, as soon as it and every earlier
+/// result are ready.
+pub(crate) fn for_each_ordered<T, R, W, E>(items: &[T], jobs: usize, work: W, mut emit: E)
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    E: FnMut(R),
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                if idx >= items.len() || sender.send((idx, work(&items[idx]))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+        let mut pending: Vec<Option<R>> = (0..items.len()).map(|_| None).collect();
+        let mut emitted = 0;
+        for (idx, result) in receiver {
+            pending[idx] = Some(result);
+            while let Some(result) = pending.get_mut(emitted).and_then(Option::take) {
+                emit(result);
+                emitted += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_results_are_emitted_in_order() {
+        let items = (0..20).collect::<Vec<u64>>();
+        for jobs in [1, 4, 32] {
+            let mut results = Vec::new();
+            for_each_ordered(
+                &items,
+                jobs,
+                |&item| {
+                    // Make earlier items finish last.
+                    thread::sleep(Duration::from_millis(20 - item));
+                    item * item
+                },
+                |result| results.push(result),
+            );
+            assert_eq!(items.iter().map(|i| i * i).collect::<Vec<_>>(), results);
+        }
+    }
+}
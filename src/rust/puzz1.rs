@@ -1,20 +1,18 @@
-use std::error::Error;
-use std::path::Path;
+//! Day 1: Sonar Sweep.
 
-static INPUT_PATH: &str = "input/puzz1.csv";
+use crate::error::AocError;
+use crate::input;
+use crate::solver::Solver;
 
-fn get_input(path: &Path) -> Result<Vec<i32>, Box<dyn Error>> {
-    let mut rdr = csv::ReaderBuilder::new()
-        .has_headers(false)
-        .from_path(&path)?;
-    let mut input: Vec<i32> = Vec::new();
-    for result in rdr.records() {
-        input.push(result?.as_slice().parse::<i32>()?);
-    }
-    Ok(input)
+static INPUT_FILE: &str = "puzz1.csv";
+
+/// Parse one depth measurement per line.
+pub fn parse_input(raw: &str) -> Result<Vec<i32>, AocError> {
+    input::parse_lines(raw, |line| line.parse())
 }
 
-fn diff(slc: &[i32]) -> Vec<i32> {
+/// Differences between consecutive elements of `slc`.
+pub fn diff(slc: &[i32]) -> Vec<i32> {
     slc[0..slc.len() - 1]
         .iter()
         .zip(slc[1..].iter())
@@ -22,29 +20,51 @@ fn diff(slc: &[i32]) -> Vec<i32> {
         .collect::<Vec<i32>>()
 }
 
-fn moving_sum(slc: &[i32], window_size: usize) -> Vec<i32> {
+/// Sums over every window of `window_size` consecutive elements.
+pub fn moving_sum(slc: &[i32], window_size: usize) -> Vec<i32> {
     slc.windows(window_size)
         .map(|w| w.iter().sum())
         .collect::<Vec<i32>>()
 }
 
-fn count_positive(slc: &[i32]) -> u32 {
+/// Number of strictly positive elements.
+pub fn count_positive(slc: &[i32]) -> u32 {
     slc.iter()
         .fold(0u32, |acc, &v| if v > 0 { acc + 1 } else { acc })
 }
 
-pub(crate) fn puzz1() {
-    let input = get_input(Path::new(INPUT_PATH)).expect("Could not read input data");
-    let cnt_larger = count_positive(&diff(&input));
-    println!(
-        "{} measurements larger than the previous measurement",
-        cnt_larger
-    );
-    let cnt_larger = count_positive(&diff(&moving_sum(&input, 3)));
-    println!(
-        "{} values larger than the previous value in window-summed input",
-        cnt_larger
-    );
+/// [`Solver`] for day 1.
+pub struct Puzz1;
+
+impl Solver for Puzz1 {
+    const DAY: usize = 1;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Vec<i32>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, input: &Self::Input) -> Self::PartOne {
+        count_positive(&diff(input))
+    }
+    fn part_two(&self, input: &Self::Input) -> Self::PartTwo {
+        count_positive(&diff(&moving_sum(input, 3)))
+    }
+    fn describe_part_one(&self, cnt_larger: &Self::PartOne) -> String {
+        format!(
+            "{} measurements larger than the previous measurement",
+            cnt_larger
+        )
+    }
+    fn describe_part_two(&self, cnt_larger: &Self::PartTwo) -> String {
+        format!(
+            "{} values larger than the previous value in window-summed input",
+            cnt_larger
+        )
+    }
 }
 
 #[cfg(test)]
@@ -63,4 +83,12 @@ mod tests {
         let count = count_positive(&diff(&moving_sum(&TEST_INPUT, 3)));
         assert_eq!(5, count);
     }
+
+    #[test]
+    fn test_solver() {
+        let raw = TEST_INPUT.map(|v| v.to_string()).join("\n");
+        let input = Puzz1.parse(&raw).expect("Could not parse input");
+        assert_eq!(7, Puzz1.part_one(&input));
+        assert_eq!(5, Puzz1.part_two(&input));
+    }
 }
@@ -0,0 +1,207 @@
+//! Day 10: Syntax Scoring.
+
+use crate::error::AocError;
+use crate::input;
+use crate::solver::Solver;
+
+static INPUT_FILE: &str = "puzz10.txt";
+
+/// Parse one line of chunk brackets per line.
+pub fn parse_input(raw: &str) -> Result<Vec<String>, AocError> {
+    input::parse_lines(raw, |line| {
+        match line
+            .text
+            .char_indices()
+            .find(|&(_, c)| !"()[]{}<>".contains(c))
+        {
+            Some((idx, c)) => Err(line.error(&line.text[idx..], format!("Unexpected {:?}", c))),
+            None => Ok(String::from(line.text)),
+        }
+    })
+}
+
+/// Outcome of checking one line of chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineStatus {
+    /// A chunk closed with the wrong character, at a 0-based char `position`.
+    Corrupted {
+        illegal: char,
+        position: usize,
+    },
+    /// Every chunk closed correctly but some were left open; `completion`
+    /// closes them.
+    Incomplete {
+        completion: String,
+    },
+    Complete,
+}
+
+fn closing(open: char) -> Option<char> {
+    match open {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        '<' => Some('>'),
+        _ => None,
+    }
+}
+
+/// Classify `line` using a stack of the closing characters still expected.
+pub fn check_line(line: &str) -> LineStatus {
+    let mut expected: Vec<char> = Vec::new();
+    for (position, c) in line.chars().enumerate() {
+        match closing(c) {
+            Some(close) => expected.push(close),
+            None => {
+                if expected.pop() != Some(c) {
+                    return LineStatus::Corrupted {
+                        illegal: c,
+                        position,
+                    };
+                }
+            }
+        }
+    }
+    if expected.is_empty() {
+        LineStatus::Complete
+    } else {
+        LineStatus::Incomplete {
+            completion: expected.iter().rev().collect(),
+        }
+    }
+}
+
+/// Points for a corrupted line whose first illegal character is `illegal`.
+pub fn syntax_error_score(illegal: char) -> u64 {
+    match illegal {
+        ')' => 3,
+        ']' => 57,
+        '}' => 1197,
+        '>' => 25137,
+        _ => 0,
+    }
+}
+
+/// Score of the `completion` string that closes an incomplete line.
+pub fn autocomplete_score(completion: &str) -> u64 {
+    completion.chars().fold(0, |score, c| {
+        let value = match c {
+            ')' => 1,
+            ']' => 2,
+            '}' => 3,
+            '>' => 4,
+            _ => 0,
+        };
+        score * 5 + value
+    })
+}
+
+/// Total syntax error score of the corrupted lines.
+pub fn total_syntax_error_score(lines: &[String]) -> u64 {
+    lines
+        .iter()
+        .map(|line| match check_line(line) {
+            LineStatus::Corrupted { illegal, .. } => syntax_error_score(illegal),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Median autocomplete score of the incomplete lines, or 0 if there are none.
+pub fn median_autocomplete_score(lines: &[String]) -> u64 {
+    let mut scores = lines
+        .iter()
+        .filter_map(|line| match check_line(line) {
+            LineStatus::Incomplete { completion } => Some(autocomplete_score(&completion)),
+            _ => None,
+        })
+        .collect::<Vec<u64>>();
+    if scores.is_empty() {
+        return 0;
+    }
+    scores.sort_unstable();
+    scores[scores.len() / 2]
+}
+
+/// [`Solver`] for day 10.
+pub struct Puzz10;
+
+impl Solver for Puzz10 {
+    const DAY: usize = 10;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Vec<String>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, lines: &Self::Input) -> Self::PartOne {
+        total_syntax_error_score(lines)
+    }
+    fn part_two(&self, lines: &Self::Input) -> Self::PartTwo {
+        median_autocomplete_score(lines)
+    }
+    fn describe_part_one(&self, score: &Self::PartOne) -> String {
+        format!("Part One: Total syntax error score: {}", score)
+    }
+    fn describe_part_two(&self, score: &Self::PartTwo) -> String {
+        format!("Part Two: Middle autocomplete score: {}", score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+    #[test]
+    fn test_part_one() {
+        let lines = parse_input(TEST_INPUT).unwrap();
+        let illegal = lines
+            .iter()
+            .filter_map(|line| match check_line(line) {
+                LineStatus::Corrupted { illegal, .. } => Some(illegal),
+                _ => None,
+            })
+            .collect::<String>();
+        assert_eq!("})])>", illegal);
+        assert_eq!(
+            LineStatus::Corrupted {
+                illegal: '}',
+                position: 12
+            },
+            check_line(&lines[2])
+        );
+        assert_eq!(26397, total_syntax_error_score(&lines));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let lines = parse_input(TEST_INPUT).unwrap();
+        let completions = lines
+            .iter()
+            .filter_map(|line| match check_line(line) {
+                LineStatus::Incomplete { completion } => Some(completion),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+        assert_eq!(
+            vec!["}}]])})]", ")}>]})", "}}>}>))))", "]]}}]}]}>", "])}>"],
+            completions
+        );
+        assert_eq!(288957, autocomplete_score("}}]])})]"));
+        assert_eq!(288957, median_autocomplete_score(&lines));
+        assert_eq!(LineStatus::Complete, check_line("{()()()}"));
+    }
+}
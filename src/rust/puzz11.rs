@@ -0,0 +1,179 @@
+//! Day 11: Dumbo Octopus.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::error::AocError;
+use crate::input::{self, Grid};
+use crate::solver::{Answer, Solver};
+
+static INPUT_FILE: &str = "puzz11.txt";
+
+/// Parse the energy level grid, one digit per octopus.
+pub fn parse_input(raw: &str) -> Result<Grid<u8>, AocError> {
+    input::grid(raw, |c| c.to_digit(10).map(|energy| energy as u8))
+}
+
+/// Advance one step, returning how many octopuses flashed.
+///
+/// Every energy level goes up by one; each octopus above 9 then flashes once,
+/// raising its neighbours, which may flash in turn. Flashed octopuses end the
+/// step at 0.
+pub fn step(energy: &mut Grid<u8>) -> usize {
+    let mut to_flash = Vec::new();
+    for y in 0..energy.height {
+        for x in 0..energy.width {
+            energy[(x, y)] += 1;
+            if energy[(x, y)] == 10 {
+                to_flash.push((x, y));
+            }
+        }
+    }
+    let mut num_flashes = 0;
+    while let Some((x, y)) = to_flash.pop() {
+        num_flashes += 1;
+        for (nx, ny) in energy.neighbours_with_diagonals(x, y).collect::<Vec<_>>() {
+            energy[(nx, ny)] += 1;
+            if energy[(nx, ny)] == 10 {
+                to_flash.push((nx, ny));
+            }
+        }
+    }
+    for level in energy.cells.iter_mut() {
+        if *level > 9 {
+            *level = 0;
+        }
+    }
+    num_flashes
+}
+
+/// Total number of flashes over the first `num_steps` steps.
+pub fn count_flashes(energy: &Grid<u8>, num_steps: usize) -> usize {
+    let mut energy = energy.clone();
+    (0..num_steps).map(|_| step(&mut energy)).sum()
+}
+
+/// The first step on which every octopus flashes, if there is one; its
+/// `Display` is the step number, or `never`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SynchronisedStep {
+    pub step: Option<usize>,
+}
+
+impl fmt::Display for SynchronisedStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.step {
+            Some(step) => write!(f, "{}", step),
+            None => write!(f, "never"),
+        }
+    }
+}
+
+impl Answer for SynchronisedStep {}
+
+/// The first step, counting from 1, during which every octopus flashes.
+///
+/// There are finitely many grids, so the octopuses either synchronise or
+/// return to a grid already seen and repeat forever without doing so, in
+/// which case the step is `None`.
+pub fn first_synchronised_step(energy: &Grid<u8>) -> SynchronisedStep {
+    let mut energy = energy.clone();
+    let num_octopuses = energy.cells.len();
+    let mut seen = HashSet::new();
+    let mut step_number = 0;
+    while seen.insert(energy.cells.clone()) {
+        step_number += 1;
+        if step(&mut energy) == num_octopuses {
+            return SynchronisedStep {
+                step: Some(step_number),
+            };
+        }
+    }
+    SynchronisedStep { step: None }
+}
+
+/// [`Solver`] for day 11.
+pub struct Puzz11;
+
+impl Solver for Puzz11 {
+    const DAY: usize = 11;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Grid<u8>;
+    type PartOne = usize;
+    type PartTwo = SynchronisedStep;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, energy: &Self::Input) -> Self::PartOne {
+        count_flashes(energy, 100)
+    }
+    fn part_two(&self, energy: &Self::Input) -> Self::PartTwo {
+        first_synchronised_step(energy)
+    }
+    fn describe_part_one(&self, num_flashes: &Self::PartOne) -> String {
+        format!("Part One: Flashes after 100 steps: {}", num_flashes)
+    }
+    fn describe_part_two(&self, step: &Self::PartTwo) -> String {
+        match step.step {
+            Some(step) => format!("Part Two: Every octopus first flashes during step {}", step),
+            None => String::from("Part Two: The octopuses never all flash at once"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+    #[test]
+    fn test_step() {
+        let mut energy = parse_input("11111\n19991\n19191\n19991\n11111").unwrap();
+        assert_eq!(9, step(&mut energy));
+        assert_eq!("34543\n40004\n50005\n40004\n34543", energy.to_string());
+        assert_eq!(0, step(&mut energy));
+        assert_eq!("45654\n51115\n61116\n51115\n45654", energy.to_string());
+
+        let mut energy = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(0, step(&mut energy));
+        assert_eq!(35, step(&mut energy));
+        let after_step_2 = "8807476555
+5089087054
+8597889608
+8485769600
+8700908800
+6600088989
+6800005943
+0000007456
+9000000876
+8700006848";
+        assert_eq!(after_step_2, energy.to_string());
+    }
+
+    #[test]
+    fn test_part_one() {
+        let energy = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(204, count_flashes(&energy, 10));
+        assert_eq!(1656, count_flashes(&energy, 100));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let energy = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(Some(195), first_synchronised_step(&energy).step);
+        let never = parse_input("002").unwrap();
+        assert_eq!(None, first_synchronised_step(&never).step);
+        assert_eq!("never", Puzz11.part_two(&never).to_string());
+    }
+}
@@ -0,0 +1,225 @@
+//! Day 12: Passage Pathing.
+
+use crate::error::AocError;
+use crate::input;
+use crate::solver::Solver;
+
+static INPUT_FILE: &str = "puzz12.txt";
+
+/// The caves and the passages between them, with caves referred to by index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaveSystem {
+    names: Vec<String>,
+    connections: Vec<Vec<usize>>,
+}
+
+impl CaveSystem {
+    /// Index of the cave called `name`, if there is one.
+    pub fn cave(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+    pub fn name(&self, cave: usize) -> &str {
+        &self.names[cave]
+    }
+    pub fn is_small(&self, cave: usize) -> bool {
+        self.names[cave].chars().all(|c| c.is_ascii_lowercase())
+    }
+    /// Caves directly connected to `cave`.
+    pub fn connections(&self, cave: usize) -> &[usize] {
+        &self.connections[cave]
+    }
+    fn add_cave(&mut self, name: &str) -> usize {
+        self.cave(name).unwrap_or_else(|| {
+            self.names.push(String::from(name));
+            self.connections.push(Vec::new());
+            self.names.len() - 1
+        })
+    }
+}
+
+/// Parse one `a-b` passage per line.
+pub fn parse_input(raw: &str) -> Result<CaveSystem, AocError> {
+    let mut caves = CaveSystem {
+        names: Vec::new(),
+        connections: Vec::new(),
+    };
+    input::parse_lines(raw, |line| {
+        let (a, b) = line
+            .text
+            .split_once('-')
+            .ok_or_else(|| line.error(line.text, "Expected `cave-cave`"))?;
+        for name in [a, b] {
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(line.error(name, format!("Invalid cave name {:?}", name)));
+            }
+        }
+        let (a, b) = (caves.add_cave(a), caves.add_cave(b));
+        if !caves.is_small(a) && !caves.is_small(b) {
+            return Err(line.error(
+                line.text,
+                "Two connected big caves allow infinitely many paths",
+            ));
+        }
+        caves.connections[a].push(b);
+        caves.connections[b].push(a);
+        Ok(())
+    })?;
+    for name in ["start", "end"] {
+        if caves.cave(name).is_none() {
+            return Err(AocError::invalid(format!("No {} cave", name)));
+        }
+    }
+    if caves.names.len() > 64 {
+        return Err(AocError::invalid("At most 64 caves are supported"));
+    }
+    Ok(caves)
+}
+
+struct PathCounter<'a> {
+    caves: &'a CaveSystem,
+    start: usize,
+    end: usize,
+}
+
+impl PathCounter<'_> {
+    /// Paths from `cave` to the end cave, given the small caves already
+    /// `visited` as a bitmask and whether one of them may be visited again.
+    fn count(&self, cave: usize, visited: u64, may_revisit: bool) -> usize {
+        if cave == self.end {
+            return 1;
+        }
+        let visited = if self.caves.is_small(cave) {
+            visited | (1 << cave)
+        } else {
+            visited
+        };
+        self.caves
+            .connections(cave)
+            .iter()
+            .map(|&next| {
+                if visited & (1 << next) == 0 {
+                    self.count(next, visited, may_revisit)
+                } else if may_revisit && next != self.start {
+                    self.count(next, visited, false)
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+}
+
+/// Number of paths from `start` to `end` visiting small caves at most once,
+/// except that with `may_revisit` a single small cave other than `start` may be
+/// visited twice.
+pub fn count_paths(caves: &CaveSystem, may_revisit: bool) -> usize {
+    let start = caves.cave("start").expect("Checked while parsing");
+    let end = caves.cave("end").expect("Checked while parsing");
+    PathCounter { caves, start, end }.count(start, 0, may_revisit)
+}
+
+/// [`Solver`] for day 12.
+pub struct Puzz12;
+
+impl Solver for Puzz12 {
+    const DAY: usize = 12;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = CaveSystem;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, caves: &Self::Input) -> Self::PartOne {
+        count_paths(caves, false)
+    }
+    fn part_two(&self, caves: &Self::Input) -> Self::PartTwo {
+        count_paths(caves, true)
+    }
+    fn describe_part_one(&self, num_paths: &Self::PartOne) -> String {
+        format!(
+            "Part One: Paths visiting small caves at most once: {}",
+            num_paths
+        )
+    }
+    fn describe_part_two(&self, num_paths: &Self::PartTwo) -> String {
+        format!(
+            "Part Two: Paths visiting a single small cave twice: {}",
+            num_paths
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT_SMALL: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+    static TEST_INPUT_MEDIUM: &str = "dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc";
+    static TEST_INPUT_LARGE: &str = "fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW";
+
+    #[test]
+    fn test_part_one() {
+        let paths = |raw| count_paths(&parse_input(raw).unwrap(), false);
+        assert_eq!(10, paths(TEST_INPUT_SMALL));
+        assert_eq!(19, paths(TEST_INPUT_MEDIUM));
+        assert_eq!(226, paths(TEST_INPUT_LARGE));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let paths = |raw| count_paths(&parse_input(raw).unwrap(), true);
+        assert_eq!(36, paths(TEST_INPUT_SMALL));
+        assert_eq!(103, paths(TEST_INPUT_MEDIUM));
+        assert_eq!(3509, paths(TEST_INPUT_LARGE));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((2, 1)),
+            parse_input("start-A\nA-B\nA-end").unwrap_err().location()
+        );
+        assert_eq!(
+            Some((1, 7)),
+            parse_input("start-a1").unwrap_err().location()
+        );
+        assert!(matches!(
+            parse_input("start-A\nA-b"),
+            Err(AocError::InvalidData(_))
+        ));
+    }
+}
@@ -0,0 +1,350 @@
+//! Day 13: Transparent Origami.
+
+use std::fmt;
+
+use crate::error::AocError;
+use crate::input::{self, Line};
+use crate::puzz5::Point;
+use crate::solver::{Answer, Solver};
+
+static INPUT_FILE: &str = "puzz13.txt";
+
+/// Width and height of a letter in the puzzle's font; letters are separated
+/// by one blank column.
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+/// Every letter of the font that is known to appear in puzzle answers.
+static FONT: [(char, &str); 17] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// A fold of the paper along a vertical (`x=`) or horizontal (`y=`) line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Fold {
+    AlongX(usize),
+    AlongY(usize),
+}
+
+impl Fold {
+    /// Where the dot at `point` ends up after folding.
+    pub fn apply(&self, point: Point) -> Point {
+        match *self {
+            Fold::AlongX(x) if point.x > x => Point::new(2 * x - point.x, point.y),
+            Fold::AlongY(y) if point.y > y => Point::new(point.x, 2 * y - point.y),
+            _ => point,
+        }
+    }
+    /// Whether the dot at `point` lies on the fold line itself.
+    pub fn is_on_line(&self, point: Point) -> bool {
+        match *self {
+            Fold::AlongX(x) => point.x == x,
+            Fold::AlongY(y) => point.y == y,
+        }
+    }
+}
+
+/// The dots on the transparent paper and the folds to make, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manual {
+    pub dots: Vec<Point>,
+    pub folds: Vec<Fold>,
+}
+
+fn parse_dot(line: &Line) -> Result<Point, AocError> {
+    let (x, y) = line
+        .text
+        .split_once(',')
+        .ok_or_else(|| line.error(line.text, "Expected `x,y`"))?;
+    Ok(Point::new(line.parse_token(x)?, line.parse_token(y)?))
+}
+
+fn parse_fold(line: &Line) -> Result<Fold, AocError> {
+    let instruction = line
+        .text
+        .strip_prefix("fold along ")
+        .ok_or_else(|| line.error(line.text, "Expected `fold along x=N` or `fold along y=N`"))?;
+    let (axis, position) = instruction
+        .split_once('=')
+        .ok_or_else(|| line.error(instruction, "Expected `x=N` or `y=N`"))?;
+    match axis {
+        "x" => Ok(Fold::AlongX(line.parse_token(position)?)),
+        "y" => Ok(Fold::AlongY(line.parse_token(position)?)),
+        _ => Err(line.error(axis, format!("Unknown axis {:?}", axis))),
+    }
+}
+
+/// Parse the `x,y` dots and then, after a blank line, at least one fold
+/// instruction.
+///
+/// Each fold must fold the paper at least in half so that no dot ends up at a
+/// negative coordinate, and no dot may lie on a fold line when it is folded.
+pub fn parse_input(raw: &str) -> Result<Manual, AocError> {
+    let blocks = input::blocks(raw);
+    let (dot_lines, fold_lines) = match blocks.as_slice() {
+        [dots, folds] => (dots, folds),
+        _ => {
+            return Err(AocError::invalid(format!(
+                "Expected dots and folds separated by a blank line, found {} blocks",
+                blocks.len()
+            )))
+        }
+    };
+    let dots = dot_lines
+        .iter()
+        .map(parse_dot)
+        .collect::<Result<Vec<Point>, AocError>>()?;
+    let mut max_x = dots.iter().map(|p| p.x).max().unwrap_or(0);
+    let mut max_y = dots.iter().map(|p| p.y).max().unwrap_or(0);
+    let mut positions = dots.clone();
+    let mut folds = Vec::with_capacity(fold_lines.len());
+    for line in fold_lines {
+        let fold = parse_fold(line)?;
+        let (max, position) = match fold {
+            Fold::AlongX(x) => (&mut max_x, x),
+            Fold::AlongY(y) => (&mut max_y, y),
+        };
+        if *max > 2 * position {
+            return Err(line.error(line.text, "Fold does not cover the whole paper"));
+        }
+        if let Some(idx) = positions.iter().position(|&p| fold.is_on_line(p)) {
+            return Err(line.error(
+                line.text,
+                format!(
+                    "Fold line passes through the dot on line {}",
+                    dot_lines[idx].number
+                ),
+            ));
+        }
+        for p in positions.iter_mut() {
+            *p = fold.apply(*p);
+        }
+        *max = (*max).min(position.saturating_sub(1));
+        folds.push(fold);
+    }
+    Ok(Manual { dots, folds })
+}
+
+/// The visible dots after `fold`, sorted and without duplicates.
+pub fn fold_paper(dots: &[Point], fold: Fold) -> Vec<Point> {
+    let mut folded = dots.iter().map(|&p| fold.apply(p)).collect::<Vec<Point>>();
+    folded.sort_unstable();
+    folded.dedup();
+    folded
+}
+
+/// The dots drawn as `#` on a background of `.`, one line per row.
+pub fn render(dots: &[Point]) -> String {
+    let width = dots.iter().map(|p| p.x + 1).max().unwrap_or(0);
+    let height = dots.iter().map(|p| p.y + 1).max().unwrap_or(0);
+    let mut rows = vec![vec!['.'; width]; height];
+    for p in dots {
+        rows[p.y][p.x] = '#';
+    }
+    rows.into_iter()
+        .map(|row| row.into_iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Read the letters drawn by `dots` in the puzzle's font, or `None` if any
+/// part of the picture is not a known letter.
+pub fn recognise_letters(dots: &[Point]) -> Option<String> {
+    let width = dots.iter().map(|p| p.x + 1).max()?;
+    if dots.iter().any(|p| p.y >= GLYPH_HEIGHT) {
+        return None;
+    }
+    let is_dot = |x: usize, y: usize| dots.contains(&Point::new(x, y));
+    let num_letters = width.div_ceil(GLYPH_WIDTH + 1);
+    let mut letters = String::with_capacity(num_letters);
+    for lidx in 0..num_letters {
+        let left = lidx * (GLYPH_WIDTH + 1);
+        let glyph = (0..GLYPH_HEIGHT)
+            .flat_map(|y| (left..left + GLYPH_WIDTH).map(move |x| (x, y)))
+            .map(|(x, y)| if is_dot(x, y) { '#' } else { '.' })
+            .collect::<String>();
+        let separator = left + GLYPH_WIDTH;
+        if (0..GLYPH_HEIGHT).any(|y| is_dot(separator, y)) {
+            return None;
+        }
+        let &(letter, _) = FONT.iter().find(|(_, g)| *g == glyph)?;
+        letters.push(letter);
+    }
+    Some(letters)
+}
+
+/// The code revealed by the fully folded paper; its `Display` is the letters
+/// if they could be recognised and the picture otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Code {
+    pub letters: Option<String>,
+    pub picture: String,
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.letters {
+            Some(letters) => write!(f, "{}", letters),
+            None => write!(f, "{}", self.picture),
+        }
+    }
+}
+
+impl Answer for Code {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![("picture", self.picture.clone())]
+    }
+}
+
+/// Make every fold and read the resulting code.
+pub fn fold_all(manual: &Manual) -> Code {
+    let dots = manual
+        .folds
+        .iter()
+        .fold(manual.dots.clone(), |dots, &fold| fold_paper(&dots, fold));
+    Code {
+        letters: recognise_letters(&dots),
+        picture: render(&dots),
+    }
+}
+
+/// [`Solver`] for day 13.
+pub struct Puzz13;
+
+impl Solver for Puzz13 {
+    const DAY: usize = 13;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Manual;
+    type PartOne = usize;
+    type PartTwo = Code;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, manual: &Self::Input) -> Self::PartOne {
+        fold_paper(&manual.dots, manual.folds[0]).len()
+    }
+    fn part_two(&self, manual: &Self::Input) -> Self::PartTwo {
+        fold_all(manual)
+    }
+    fn describe_part_one(&self, num_dots: &Self::PartOne) -> String {
+        format!("Part One: Dots visible after the first fold: {}", num_dots)
+    }
+    fn describe_part_two(&self, code: &Self::PartTwo) -> String {
+        match &code.letters {
+            Some(letters) => format!("Part Two: Activation code {}:\n{}", letters, code.picture),
+            None => format!("Part Two: Activation code:\n{}", code.picture),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+    #[test]
+    fn test_part_one() {
+        let manual = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(vec![Fold::AlongY(7), Fold::AlongX(5)], manual.folds);
+        assert_eq!(17, Puzz13.part_one(&manual));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let manual = parse_input(TEST_INPUT).unwrap();
+        let code = fold_all(&manual);
+        assert_eq!("#####\n#...#\n#...#\n#...#\n#####", code.picture);
+        assert_eq!(None, code.letters);
+        assert_eq!(code.picture, code.to_string());
+    }
+
+    #[test]
+    fn test_recognise_letters() {
+        let picture = "\
+.##..###..#..#.####
+#..#.#..#.#..#.#...
+#..#.###..####.###.
+####.#..#.#..#.#...
+#..#.#..#.#..#.#...
+#..#.###..#..#.####";
+        let dots = picture
+            .lines()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.char_indices()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(x, _)| Point::new(x, y))
+            })
+            .collect::<Vec<Point>>();
+        assert_eq!(picture, render(&dots));
+        assert_eq!(Some(String::from("ABHE")), recognise_letters(&dots));
+        let garbled = dots[1..].to_vec();
+        assert_eq!(None, recognise_letters(&garbled));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((3, 12)),
+            parse_input("1,2\n\nfold along z=1").unwrap_err().location()
+        );
+        assert_eq!(
+            Some((3, 1)),
+            parse_input("1,9\n\nfold along y=2").unwrap_err().location()
+        );
+        assert_eq!(
+            Some((1, 3)),
+            parse_input("1,x\n\nfold along y=2").unwrap_err().location()
+        );
+        assert_eq!(
+            Some((4, 1)),
+            parse_input("1,2\n4,2\n\nfold along x=4")
+                .unwrap_err()
+                .location()
+        );
+        assert_eq!(
+            Some((5, 1)),
+            parse_input("0,0\n6,1\n\nfold along x=4\nfold along x=2")
+                .unwrap_err()
+                .location()
+        );
+    }
+}
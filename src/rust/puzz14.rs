@@ -0,0 +1,255 @@
+//! Day 14: Extended Polymerization.
+
+use std::collections::HashMap;
+
+use crate::error::AocError;
+use crate::input::{self, Line};
+use crate::solver::Solver;
+
+static INPUT_FILE: &str = "puzz14.txt";
+
+/// Element inserted between each pair of adjacent elements.
+pub type Rules = HashMap<[u8; 2], u8>;
+
+/// Number of occurrences of every pair of adjacent elements.
+pub type PairCounts = HashMap<[u8; 2], u64>;
+
+/// The polymer template and the pair insertion rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instructions {
+    pub template: Vec<u8>,
+    pub rules: Rules,
+}
+
+fn parse_elements<'a>(line: &Line, token: &'a str) -> Result<&'a [u8], AocError> {
+    match token.char_indices().find(|(_, c)| !c.is_ascii_uppercase()) {
+        Some((idx, c)) => Err(line.error(&token[idx..], format!("Unexpected element {:?}", c))),
+        None => Ok(token.as_bytes()),
+    }
+}
+
+fn parse_rule(line: &Line) -> Result<([u8; 2], u8), AocError> {
+    let (pair, element) = line
+        .text
+        .split_once(" -> ")
+        .ok_or_else(|| line.error(line.text, "Expected `AB -> C`"))?;
+    match (parse_elements(line, pair)?, parse_elements(line, element)?) {
+        (&[a, b], &[inserted]) => Ok(([a, b], inserted)),
+        _ => Err(line.error(
+            line.text,
+            "Expected a pair of elements and a single element",
+        )),
+    }
+}
+
+/// Parse the template and then, after a blank line, one `AB -> C` rule per
+/// line.
+pub fn parse_input(raw: &str) -> Result<Instructions, AocError> {
+    let blocks = input::blocks(raw);
+    let (template, rules) = match blocks.as_slice() {
+        [template, rules] if template.len() == 1 => (template[0], rules),
+        _ => {
+            return Err(AocError::invalid(
+                "Expected a template line and rules separated by a blank line",
+            ))
+        }
+    };
+    let template = parse_elements(&template, template.text)?.to_vec();
+    let rules = rules
+        .iter()
+        .map(parse_rule)
+        .collect::<Result<Rules, AocError>>()?;
+    Ok(Instructions { template, rules })
+}
+
+/// One step of insertion, building the new polymer element by element.
+pub fn insert_simple(polymer: &[u8], rules: &Rules) -> Vec<u8> {
+    let mut inserted = Vec::with_capacity(polymer.len() * 2);
+    for pair in polymer.windows(2) {
+        inserted.push(pair[0]);
+        if let Some(&element) = rules.get(&[pair[0], pair[1]]) {
+            inserted.push(element);
+        }
+    }
+    inserted.extend(polymer.last());
+    inserted
+}
+
+/// Number of occurrences of every pair of adjacent elements in `polymer`.
+pub fn pair_counts(polymer: &[u8]) -> PairCounts {
+    let mut counts = PairCounts::new();
+    for pair in polymer.windows(2) {
+        *counts.entry([pair[0], pair[1]]).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// One step of insertion, tracking only how often each pair occurs: every
+/// `AB` with rule `AB -> C` becomes an `AC` and a `CB`.
+pub fn insert_pairs(pairs: &PairCounts, rules: &Rules) -> PairCounts {
+    let mut inserted = PairCounts::with_capacity(pairs.len());
+    for (&[a, b], &count) in pairs.iter() {
+        match rules.get(&[a, b]) {
+            Some(&c) => {
+                *inserted.entry([a, c]).or_insert(0) += count;
+                *inserted.entry([c, b]).or_insert(0) += count;
+            }
+            None => *inserted.entry([a, b]).or_insert(0) += count,
+        }
+    }
+    inserted
+}
+
+/// Most common minus least common element count.
+fn spread(counts: &HashMap<u8, u64>) -> u64 {
+    let most = counts.values().max().copied().unwrap_or(0);
+    let least = counts.values().min().copied().unwrap_or(0);
+    most - least
+}
+
+/// Number of occurrences of every element in `polymer`.
+pub fn element_counts(polymer: &[u8]) -> HashMap<u8, u64> {
+    let mut counts = HashMap::new();
+    for &element in polymer {
+        *counts.entry(element).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Element counts of a polymer from its pair counts: every element starts
+/// exactly one pair except the last element, which never changes.
+pub fn element_counts_from_pairs(pairs: &PairCounts, last: u8) -> HashMap<u8, u64> {
+    let mut counts = HashMap::from([(last, 1)]);
+    for (&[a, _], &count) in pairs.iter() {
+        *counts.entry(a).or_insert(0) += count;
+    }
+    counts
+}
+
+/// Spread of element counts after `num_steps`, building the whole polymer.
+pub fn spread_after_simple(instructions: &Instructions, num_steps: usize) -> u64 {
+    let polymer = (0..num_steps).fold(instructions.template.clone(), |polymer, _| {
+        insert_simple(&polymer, &instructions.rules)
+    });
+    spread(&element_counts(&polymer))
+}
+
+/// Spread of element counts after `num_steps`, counting pairs.
+pub fn spread_after(instructions: &Instructions, num_steps: usize) -> u64 {
+    let pairs = (0..num_steps).fold(pair_counts(&instructions.template), |pairs, _| {
+        insert_pairs(&pairs, &instructions.rules)
+    });
+    match instructions.template.last() {
+        Some(&last) => spread(&element_counts_from_pairs(&pairs, last)),
+        None => 0,
+    }
+}
+
+/// [`Solver`] for day 14.
+pub struct Puzz14;
+
+impl Solver for Puzz14 {
+    const DAY: usize = 14;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Instructions;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, instructions: &Self::Input) -> Self::PartOne {
+        spread_after_simple(instructions, 10)
+    }
+    fn part_two(&self, instructions: &Self::Input) -> Self::PartTwo {
+        spread_after(instructions, 40)
+    }
+    fn describe_part_one(&self, spread: &Self::PartOne) -> String {
+        format!(
+            "Part One: Most minus least common element after 10 steps: {}",
+            spread
+        )
+    }
+    fn describe_part_two(&self, spread: &Self::PartTwo) -> String {
+        format!(
+            "Part Two: Most minus least common element after 40 steps: {}",
+            spread
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+    #[test]
+    fn test_part_one() {
+        let instructions = parse_input(TEST_INPUT).unwrap();
+        let mut polymer = instructions.template.clone();
+        for expected in ["NCNBCHB", "NBCCNBBBCBHCB", "NBBBCNCCNBBNBNBBCHBHHBCHB"] {
+            polymer = insert_simple(&polymer, &instructions.rules);
+            assert_eq!(expected.as_bytes(), polymer.as_slice());
+        }
+        assert_eq!(1588, spread_after_simple(&instructions, 10));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let instructions = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(1588, spread_after(&instructions, 10));
+        assert_eq!(2188189693529, spread_after(&instructions, 40));
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        let instructions = parse_input(TEST_INPUT).unwrap();
+        let mut polymer = instructions.template.clone();
+        let mut pairs = pair_counts(&polymer);
+        for _ in 0..12 {
+            polymer = insert_simple(&polymer, &instructions.rules);
+            pairs = insert_pairs(&pairs, &instructions.rules);
+            assert_eq!(pair_counts(&polymer), pairs);
+            assert_eq!(
+                element_counts(&polymer),
+                element_counts_from_pairs(&pairs, b'B')
+            );
+        }
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((1, 3)),
+            parse_input("NNcB\n\nCH -> B").unwrap_err().location()
+        );
+        assert_eq!(
+            Some((4, 1)),
+            parse_input("NNCB\n\nCH -> B\nCHB -> N")
+                .unwrap_err()
+                .location()
+        );
+        assert!(matches!(
+            parse_input("NNCB\nCH -> B"),
+            Err(AocError::InvalidData(_))
+        ));
+    }
+}
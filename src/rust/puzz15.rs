@@ -0,0 +1,141 @@
+//! Day 15: Chiton.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::error::AocError;
+use crate::input::{self, Grid};
+use crate::solver::Solver;
+
+static INPUT_FILE: &str = "puzz15.txt";
+
+/// Parse the risk level map, one digit from 1 to 9 per position.
+pub fn parse_input(raw: &str) -> Result<Grid<u8>, AocError> {
+    input::grid(raw, |c| match c.to_digit(10) {
+        Some(risk @ 1..=9) => Some(risk as u8),
+        _ => None,
+    })
+}
+
+/// The risk map repeated `tiles` times in both directions, with every tile's
+/// risk levels one higher than the tile above or to the left of it, wrapping
+/// from 9 back to 1. Risks are computed on demand.
+#[derive(Debug, Copy, Clone)]
+pub struct TiledMap<'a> {
+    base: &'a Grid<u8>,
+    tiles: usize,
+}
+
+impl<'a> TiledMap<'a> {
+    pub fn new(base: &'a Grid<u8>, tiles: usize) -> Self {
+        Self { base, tiles }
+    }
+    pub fn width(&self) -> usize {
+        self.base.width * self.tiles
+    }
+    pub fn height(&self) -> usize {
+        self.base.height * self.tiles
+    }
+    /// Risk level of entering `(x, y)`, which must be inside the map.
+    pub fn risk(&self, x: usize, y: usize) -> u32 {
+        let (tile_x, tile_y) = (x / self.base.width, y / self.base.height);
+        let base = self.base[(x % self.base.width, y % self.base.height)] as usize;
+        ((base - 1 + tile_x + tile_y) % 9 + 1) as u32
+    }
+}
+
+/// Lowest total risk of any path from the top left to the bottom right,
+/// found with Dijkstra's algorithm; the starting position is not entered and
+/// so does not count.
+pub fn lowest_total_risk(map: &TiledMap) -> u32 {
+    let (width, height) = (map.width(), map.height());
+    let target = (width - 1, height - 1);
+    let mut best = vec![u32::MAX; width * height];
+    let mut queue = BinaryHeap::new();
+    best[0] = 0;
+    queue.push(Reverse((0, 0, 0)));
+    while let Some(Reverse((risk, x, y))) = queue.pop() {
+        if (x, y) == target {
+            return risk;
+        }
+        if risk > best[y * width + x] {
+            continue;
+        }
+        for (nx, ny) in input::neighbours(x, y, width, height) {
+            let next_risk = risk + map.risk(nx, ny);
+            if next_risk < best[ny * width + nx] {
+                best[ny * width + nx] = next_risk;
+                queue.push(Reverse((next_risk, nx, ny)));
+            }
+        }
+    }
+    unreachable!("Every position of a grid is reachable")
+}
+
+/// [`Solver`] for day 15.
+pub struct Puzz15;
+
+impl Solver for Puzz15 {
+    const DAY: usize = 15;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Grid<u8>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, risks: &Self::Input) -> Self::PartOne {
+        lowest_total_risk(&TiledMap::new(risks, 1))
+    }
+    fn part_two(&self, risks: &Self::Input) -> Self::PartTwo {
+        lowest_total_risk(&TiledMap::new(risks, 5))
+    }
+    fn describe_part_one(&self, risk: &Self::PartOne) -> String {
+        format!("Part One: Lowest total risk across the map: {}", risk)
+    }
+    fn describe_part_two(&self, risk: &Self::PartTwo) -> String {
+        format!("Part Two: Lowest total risk across the full map: {}", risk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    #[test]
+    fn test_part_one() {
+        let risks = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(40, lowest_total_risk(&TiledMap::new(&risks, 1)));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let risks = parse_input(TEST_INPUT).unwrap();
+        let map = TiledMap::new(&risks, 5);
+        assert_eq!((50, 50), (map.width(), map.height()));
+        let last_row = (0..map.width())
+            .map(|x| char::from_digit(map.risk(x, 49), 10).unwrap())
+            .collect::<String>();
+        assert_eq!(
+            "67554889357866599146897761125791887223681299833479",
+            last_row
+        );
+        let single = parse_input("8").unwrap();
+        let tiled = TiledMap::new(&single, 5);
+        let first_row = (0..5).map(|x| tiled.risk(x, 0)).collect::<Vec<u32>>();
+        assert_eq!(vec![8, 9, 1, 2, 3], first_row);
+        assert_eq!(315, lowest_total_risk(&map));
+    }
+}
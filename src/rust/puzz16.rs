@@ -0,0 +1,344 @@
+//! Day 16: Packet Decoder.
+
+use std::fmt;
+
+use crate::error::AocError;
+use crate::input;
+use crate::puzz3::bits_to_integer;
+use crate::solver::Solver;
+
+static INPUT_FILE: &str = "puzz16.txt";
+
+/// What an operator packet computes from its sub-packets.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+impl Operator {
+    fn from_type_id(type_id: usize) -> Option<Self> {
+        match type_id {
+            0 => Some(Operator::Sum),
+            1 => Some(Operator::Product),
+            2 => Some(Operator::Minimum),
+            3 => Some(Operator::Maximum),
+            5 => Some(Operator::GreaterThan),
+            6 => Some(Operator::LessThan),
+            7 => Some(Operator::EqualTo),
+            _ => None,
+        }
+    }
+    fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo
+        )
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Operator::Sum => "sum",
+            Operator::Product => "product",
+            Operator::Minimum => "minimum",
+            Operator::Maximum => "maximum",
+            Operator::GreaterThan => "greater than",
+            Operator::LessThan => "less than",
+            Operator::EqualTo => "equal to",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Contents {
+    Literal(u64),
+    Operator {
+        operator: Operator,
+        subpackets: Vec<Packet>,
+    },
+}
+
+/// A decoded packet and, for operators, the tree of packets below it.
+///
+/// Its `Display` prints one packet per line, indenting sub-packets beneath
+/// their operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u8,
+    pub contents: Contents,
+}
+
+impl Packet {
+    /// Sum of the version numbers of this packet and every packet below it.
+    pub fn version_sum(&self) -> u64 {
+        let below = match &self.contents {
+            Contents::Literal(_) => 0,
+            Contents::Operator { subpackets, .. } => {
+                subpackets.iter().map(Packet::version_sum).sum()
+            }
+        };
+        self.version as u64 + below
+    }
+    /// Value of the expression this packet represents.
+    pub fn evaluate(&self) -> u64 {
+        let (operator, subpackets) = match &self.contents {
+            Contents::Literal(value) => return *value,
+            Contents::Operator {
+                operator,
+                subpackets,
+            } => (operator, subpackets),
+        };
+        let mut values = subpackets.iter().map(Packet::evaluate);
+        match operator {
+            Operator::Sum => values.sum(),
+            Operator::Product => values.product(),
+            Operator::Minimum => values.min().unwrap_or(0),
+            Operator::Maximum => values.max().unwrap_or(0),
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo => {
+                let (a, b) = (values.next(), values.next());
+                let holds = match operator {
+                    Operator::GreaterThan => a > b,
+                    Operator::LessThan => a < b,
+                    _ => a == b,
+                };
+                holds as u64
+            }
+        }
+    }
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}", "", indent = 2 * depth)?;
+        match &self.contents {
+            Contents::Literal(value) => write!(f, "{} (v{})", value, self.version),
+            Contents::Operator {
+                operator,
+                subpackets,
+            } => {
+                write!(f, "{} (v{})", operator, self.version)?;
+                for subpacket in subpackets {
+                    writeln!(f)?;
+                    subpacket.fmt_indented(f, depth + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// Reads fields from a most-significant-first stream of 0/1 values.
+struct BitReader {
+    bits: Vec<u8>,
+    pos: usize,
+}
+
+impl BitReader {
+    fn error(&self, message: impl Into<String>) -> AocError {
+        // Four bits per hexadecimal digit.
+        AocError::parse(1, self.pos / 4 + 1, message)
+    }
+    fn read(&mut self, num_bits: usize) -> Result<usize, AocError> {
+        let field = self
+            .bits
+            .get(self.pos..self.pos + num_bits)
+            .ok_or_else(|| self.error("Transmission ends in the middle of a packet"))?;
+        self.pos += num_bits;
+        Ok(bits_to_integer(field))
+    }
+    fn read_literal(&mut self) -> Result<u64, AocError> {
+        let mut value: u64 = 0;
+        loop {
+            let group = self.read(5)?;
+            if value.leading_zeros() < 4 {
+                return Err(self.error("Literal value does not fit in 64 bits"));
+            }
+            value = (value << 4) | (group & 0xf) as u64;
+            if group & 0x10 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+    fn read_packet(&mut self) -> Result<Packet, AocError> {
+        let version = self.read(3)? as u8;
+        let type_id = self.read(3)?;
+        if type_id == 4 {
+            return Ok(Packet {
+                version,
+                contents: Contents::Literal(self.read_literal()?),
+            });
+        }
+        let operator = Operator::from_type_id(type_id).expect("Type IDs have three bits");
+        let mut subpackets = Vec::new();
+        if self.read(1)? == 0 {
+            let length = self.read(15)?;
+            let end = self.pos + length;
+            while self.pos < end {
+                subpackets.push(self.read_packet()?);
+            }
+            if self.pos != end {
+                return Err(self.error("Sub-packets overrun their total length"));
+            }
+        } else {
+            let count = self.read(11)?;
+            for _ in 0..count {
+                subpackets.push(self.read_packet()?);
+            }
+        }
+        if subpackets.is_empty() || (operator.is_comparison() && subpackets.len() != 2) {
+            return Err(self.error(format!(
+                "A {} packet cannot have {} sub-packets",
+                operator,
+                subpackets.len()
+            )));
+        }
+        Ok(Packet {
+            version,
+            contents: Contents::Operator {
+                operator,
+                subpackets,
+            },
+        })
+    }
+}
+
+/// Expand hexadecimal digits into their bits, most significant first.
+pub fn hex_to_bits(hex: &str) -> Option<Vec<u8>> {
+    let mut bits = Vec::with_capacity(hex.len() * 4);
+    for c in hex.chars() {
+        let digit = c.to_digit(16)?;
+        bits.extend((0..4).rev().map(|shift| ((digit >> shift) & 1) as u8));
+    }
+    Some(bits)
+}
+
+/// Decode the outermost packet of a hexadecimal transmission, ignoring any
+/// padding after it.
+pub fn decode(hex: &str) -> Result<Packet, AocError> {
+    let bits = match hex_to_bits(hex) {
+        Some(bits) => bits,
+        None => {
+            let (idx, c) = hex
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_hexdigit())
+                .expect("A character failed to parse");
+            return Err(AocError::parse(
+                1,
+                idx + 1,
+                format!("Unexpected {:?} in hexadecimal", c),
+            ));
+        }
+    };
+    BitReader { bits, pos: 0 }.read_packet()
+}
+
+/// Parse the single line of hexadecimal transmission.
+pub fn parse_input(raw: &str) -> Result<Packet, AocError> {
+    let line = input::single_line(raw)?;
+    decode(line.text).map_err(|e| e.at_line(line.number))
+}
+
+/// [`Solver`] for day 16.
+pub struct Puzz16;
+
+impl Solver for Puzz16 {
+    const DAY: usize = 16;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Packet;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, packet: &Self::Input) -> Self::PartOne {
+        packet.version_sum()
+    }
+    fn part_two(&self, packet: &Self::Input) -> Self::PartTwo {
+        packet.evaluate()
+    }
+    fn describe_part_one(&self, sum: &Self::PartOne) -> String {
+        format!("Part One: Sum of all packet version numbers: {}", sum)
+    }
+    fn describe_part_two(&self, value: &Self::PartTwo) -> String {
+        format!("Part Two: Value of the outermost packet: {}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let literal = decode("D2FE28").unwrap();
+        assert_eq!(
+            Packet {
+                version: 6,
+                contents: Contents::Literal(2021)
+            },
+            literal
+        );
+        let by_length = decode("38006F45291200").unwrap();
+        let by_count = decode("EE00D40C823060").unwrap();
+        assert_eq!(
+            "less than (v1)\n  10 (v6)\n  20 (v2)",
+            by_length.to_string()
+        );
+        assert_eq!(
+            "maximum (v7)\n  1 (v2)\n  2 (v4)\n  3 (v1)",
+            by_count.to_string()
+        );
+    }
+
+    #[test]
+    fn test_part_one() {
+        let sums = [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ];
+        for (hex, sum) in sums {
+            assert_eq!(sum, parse_input(hex).unwrap().version_sum());
+        }
+    }
+
+    #[test]
+    fn test_part_two() {
+        let values = [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ];
+        for (hex, value) in values {
+            assert_eq!(value, parse_input(hex).unwrap().evaluate());
+        }
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((2, 3)),
+            parse_input("\nD2XE28").unwrap_err().location()
+        );
+        assert_eq!(Some((1, 5)), parse_input("D2FE2").unwrap_err().location());
+    }
+}
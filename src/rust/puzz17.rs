@@ -0,0 +1,207 @@
+//! Day 17: Trick Shot.
+
+use crate::error::AocError;
+use crate::input;
+use crate::solver::Solver;
+
+static INPUT_FILE: &str = "puzz17.txt";
+
+/// The trench the probe must land in, as inclusive ranges.
+///
+/// The target is always ahead of and below the launcher, which bounds the
+/// velocities worth trying.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TargetArea {
+    pub x_min: i32,
+    pub x_max: i32,
+    pub y_min: i32,
+    pub y_max: i32,
+}
+
+impl TargetArea {
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        (self.x_min..=self.x_max).contains(&x) && (self.y_min..=self.y_max).contains(&y)
+    }
+}
+
+/// Parse the single `target area: x=A..B, y=C..D` line.
+pub fn parse_input(raw: &str) -> Result<TargetArea, AocError> {
+    let line = input::single_line(raw)?;
+    let ranges = line
+        .text
+        .strip_prefix("target area: ")
+        .ok_or_else(|| line.error(line.text, "Expected `target area: `"))?;
+    let (x_range, y_range) = ranges
+        .split_once(", ")
+        .ok_or_else(|| line.error(ranges, "Expected `x=A..B, y=C..D`"))?;
+    let (x_min, x_max) = line.range(x_range, "x")?;
+    let (y_min, y_max) = line.range(y_range, "y")?;
+    if x_min <= 0 || y_max >= 0 {
+        return Err(line.error(
+            ranges,
+            "Only targets ahead of and below the launcher are supported",
+        ));
+    }
+    Ok(TargetArea {
+        x_min,
+        x_max,
+        y_min,
+        y_max,
+    })
+}
+
+/// Position and velocity of a probe in flight.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Probe {
+    pub x: i32,
+    pub y: i32,
+    pub vx: i32,
+    pub vy: i32,
+}
+
+impl Probe {
+    /// A probe at the launcher with the initial velocity `(vx, vy)`.
+    pub fn launch(vx: i32, vy: i32) -> Self {
+        Probe { x: 0, y: 0, vx, vy }
+    }
+    /// Move by the current velocity, then apply drag and gravity.
+    pub fn single_step(&mut self) {
+        self.x += self.vx;
+        self.y += self.vy;
+        self.vx -= self.vx.signum();
+        self.vy -= 1;
+    }
+    /// Whether the probe can no longer reach `target`: it is beyond it, or
+    /// below it and still falling.
+    pub fn has_missed(&self, target: &TargetArea) -> bool {
+        self.x > target.x_max || (self.y < target.y_min && self.vy < 0)
+    }
+}
+
+/// Highest `y` reached by a probe launched at `(vx, vy)`, if it is ever
+/// within `target` after a step.
+pub fn apex_if_hits(vx: i32, vy: i32, target: &TargetArea) -> Option<i32> {
+    let mut probe = Probe::launch(vx, vy);
+    let mut apex = 0;
+    while !probe.has_missed(target) {
+        probe.single_step();
+        apex = apex.max(probe.y);
+        if target.contains(probe.x, probe.y) {
+            return Some(apex);
+        }
+    }
+    None
+}
+
+/// Apex of every initial velocity that lands in `target`.
+///
+/// Faster horizontally than `x_max` overshoots on the first step, as does
+/// falling faster than `-y_min`; a probe thrown upwards at `vy` comes back
+/// through `y = 0` at `-vy - 1`, so upwards is bounded by the same depth.
+pub fn hitting_apexes(target: &TargetArea) -> impl Iterator<Item = i32> + '_ {
+    (1..=target.x_max).flat_map(move |vx| {
+        (target.y_min..-target.y_min).filter_map(move |vy| apex_if_hits(vx, vy, target))
+    })
+}
+
+/// [`Solver`] for day 17.
+pub struct Puzz17;
+
+impl Solver for Puzz17 {
+    const DAY: usize = 17;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = TargetArea;
+    type PartOne = i32;
+    type PartTwo = usize;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, target: &Self::Input) -> Self::PartOne {
+        hitting_apexes(target).max().unwrap_or(0)
+    }
+    fn part_two(&self, target: &Self::Input) -> Self::PartTwo {
+        hitting_apexes(target).count()
+    }
+    fn describe_part_one(&self, apex: &Self::PartOne) -> String {
+        format!(
+            "Part One: Highest position that still hits the target: {}",
+            apex
+        )
+    }
+    fn describe_part_two(&self, num_velocities: &Self::PartTwo) -> String {
+        format!(
+            "Part Two: Initial velocities that hit the target: {}",
+            num_velocities
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str = "target area: x=20..30, y=-10..-5";
+
+    #[test]
+    fn test_single_step() {
+        let target = parse_input(TEST_INPUT).unwrap();
+        let mut probe = Probe::launch(7, 2);
+        let mut trajectory = Vec::new();
+        while !target.contains(probe.x, probe.y) {
+            probe.single_step();
+            trajectory.push((probe.x, probe.y));
+        }
+        assert_eq!(
+            vec![
+                (7, 2),
+                (13, 3),
+                (18, 3),
+                (22, 2),
+                (25, 0),
+                (27, -3),
+                (28, -7)
+            ],
+            trajectory
+        );
+        assert_eq!(Some(3), apex_if_hits(7, 2, &target));
+        assert_eq!(Some(6), apex_if_hits(6, 3, &target));
+        assert_eq!(Some(0), apex_if_hits(9, 0, &target));
+        assert_eq!(None, apex_if_hits(17, -4, &target));
+    }
+
+    #[test]
+    fn test_part_one() {
+        let target = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(Some(45), apex_if_hits(6, 9, &target));
+        assert_eq!(45, Puzz17.part_one(&target));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let target = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(112, Puzz17.part_two(&target));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((1, 26)),
+            parse_input("target area: x=20..30, y=-1x..-5")
+                .unwrap_err()
+                .location()
+        );
+        assert_eq!(
+            Some((1, 16)),
+            parse_input("target area: x=30..20, y=-10..-5")
+                .unwrap_err()
+                .location()
+        );
+        assert_eq!(
+            Some((1, 14)),
+            parse_input("target area: x=20..30, y=5..10")
+                .unwrap_err()
+                .location()
+        );
+    }
+}
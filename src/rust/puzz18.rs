@@ -0,0 +1,374 @@
+//! Day 18: Snailfish.
+
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+use crate::error::AocError;
+use crate::input;
+use crate::solver::Solver;
+
+static INPUT_FILE: &str = "puzz18.txt";
+
+/// Pairs nested inside this many pairs explode.
+const MAX_DEPTH: usize = 4;
+
+/// Regular numbers of this value or more split.
+const SPLIT_AT: u32 = 10;
+
+/// A regular number and how many pairs enclose it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Element {
+    value: u32,
+    depth: usize,
+}
+
+/// A snailfish number, stored as its regular numbers from left to right each
+/// tagged with their depth.
+///
+/// Parsing does not reduce, so the steps of a reduction can be checked one at
+/// a time; addition always reduces its result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnailfishNumber {
+    elements: Vec<Element>,
+}
+
+impl SnailfishNumber {
+    /// Explode the leftmost pair of regular numbers nested too deeply,
+    /// returning whether there was one.
+    pub fn explode(&mut self) -> bool {
+        let elements = &mut self.elements;
+        let idx = match (0..elements.len().saturating_sub(1))
+            .find(|&i| elements[i].depth > MAX_DEPTH && elements[i].depth == elements[i + 1].depth)
+        {
+            Some(idx) => idx,
+            None => return false,
+        };
+        let (left, right) = (elements[idx], elements[idx + 1]);
+        if idx > 0 {
+            elements[idx - 1].value += left.value;
+        }
+        if let Some(next) = elements.get_mut(idx + 2) {
+            next.value += right.value;
+        }
+        elements.splice(
+            idx..idx + 2,
+            [Element {
+                value: 0,
+                depth: left.depth - 1,
+            }],
+        );
+        true
+    }
+    /// Split the leftmost regular number that is too large into a pair,
+    /// returning whether there was one.
+    pub fn split(&mut self) -> bool {
+        let idx = match self.elements.iter().position(|e| e.value >= SPLIT_AT) {
+            Some(idx) => idx,
+            None => return false,
+        };
+        let Element { value, depth } = self.elements[idx];
+        let half = |value| Element {
+            value,
+            depth: depth + 1,
+        };
+        self.elements
+            .splice(idx..idx + 1, [half(value / 2), half(value - value / 2)]);
+        true
+    }
+    /// A single reduction action, preferring explosions to splits; returns
+    /// `false` once the number is fully reduced.
+    pub fn reduce_step(&mut self) -> bool {
+        self.explode() || self.split()
+    }
+    pub fn reduce(&mut self) {
+        while self.reduce_step() {}
+    }
+    pub fn magnitude(&self) -> u32 {
+        fn magnitude_of(elements: &[Element], idx: &mut usize, depth: usize) -> u32 {
+            if elements[*idx].depth == depth {
+                *idx += 1;
+                return elements[*idx - 1].value;
+            }
+            let left = magnitude_of(elements, idx, depth + 1);
+            let right = magnitude_of(elements, idx, depth + 1);
+            3 * left + 2 * right
+        }
+        magnitude_of(&self.elements, &mut 0, 0)
+    }
+    fn fmt_from(&self, f: &mut fmt::Formatter<'_>, idx: &mut usize, depth: usize) -> fmt::Result {
+        let element = self.elements[*idx];
+        if element.depth == depth {
+            *idx += 1;
+            return write!(f, "{}", element.value);
+        }
+        write!(f, "[")?;
+        self.fmt_from(f, idx, depth + 1)?;
+        write!(f, ",")?;
+        self.fmt_from(f, idx, depth + 1)?;
+        write!(f, "]")
+    }
+}
+
+impl fmt::Display for SnailfishNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_from(f, &mut 0, 0)
+    }
+}
+
+/// Reads `[left,right]` pairs of pairs and regular numbers.
+struct PairParser<'a> {
+    text: &'a str,
+    pos: usize,
+    elements: Vec<Element>,
+}
+
+impl PairParser<'_> {
+    fn error(&self, message: impl Into<String>) -> AocError {
+        AocError::parse(1, self.pos + 1, message)
+    }
+    fn expect(&mut self, expected: char) -> Result<(), AocError> {
+        match self.text[self.pos..].chars().next() {
+            Some(c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("Expected {:?}, found {:?}", expected, c))),
+            None => Err(self.error(format!("Expected {:?} at end of line", expected))),
+        }
+    }
+    fn read_pair(&mut self, depth: usize) -> Result<(), AocError> {
+        self.expect('[')?;
+        self.read_element(depth + 1)?;
+        self.expect(',')?;
+        self.read_element(depth + 1)?;
+        self.expect(']')
+    }
+    fn read_element(&mut self, depth: usize) -> Result<(), AocError> {
+        let rest = &self.text[self.pos..];
+        if rest.starts_with('[') {
+            return self.read_pair(depth);
+        }
+        let len = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("Expected a pair or a regular number"));
+        }
+        let value = rest[..len]
+            .parse()
+            .map_err(|e| self.error(format!("Failed to parse {:?}: {}", &rest[..len], e)))?;
+        self.elements.push(Element { value, depth });
+        self.pos += len;
+        Ok(())
+    }
+}
+
+impl FromStr for SnailfishNumber {
+    type Err = AocError;
+    /// Parse a pair such as `[[1,2],3]`; errors are located on line 1.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = PairParser {
+            text: s,
+            pos: 0,
+            elements: Vec::new(),
+        };
+        parser.read_pair(0)?;
+        if parser.pos != s.len() {
+            return Err(parser.error("Unexpected characters after the outermost pair"));
+        }
+        Ok(SnailfishNumber {
+            elements: parser.elements,
+        })
+    }
+}
+
+impl Add for SnailfishNumber {
+    type Output = SnailfishNumber;
+    /// The pair `[self,other]`, reduced.
+    fn add(self, other: SnailfishNumber) -> SnailfishNumber {
+        let mut elements = self.elements;
+        elements.extend(other.elements);
+        for element in elements.iter_mut() {
+            element.depth += 1;
+        }
+        let mut sum = SnailfishNumber { elements };
+        sum.reduce();
+        sum
+    }
+}
+
+/// Parse one snailfish number per line.
+pub fn parse_input(raw: &str) -> Result<Vec<SnailfishNumber>, AocError> {
+    input::parse_lines(raw, |line| {
+        line.text
+            .parse::<SnailfishNumber>()
+            .map_err(|e| e.at_line(line.number))
+    })
+}
+
+/// Sum of every number in order, or `None` if there are none.
+pub fn sum_all(numbers: &[SnailfishNumber]) -> Option<SnailfishNumber> {
+    numbers.iter().cloned().reduce(|sum, number| sum + number)
+}
+
+/// Largest magnitude of the sum of two different numbers, in either order.
+pub fn largest_pair_magnitude(numbers: &[SnailfishNumber]) -> u32 {
+    let mut largest = 0;
+    for (i, a) in numbers.iter().enumerate() {
+        for (j, b) in numbers.iter().enumerate() {
+            if i != j {
+                largest = largest.max((a.clone() + b.clone()).magnitude());
+            }
+        }
+    }
+    largest
+}
+
+/// [`Solver`] for day 18.
+pub struct Puzz18;
+
+impl Solver for Puzz18 {
+    const DAY: usize = 18;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Vec<SnailfishNumber>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, numbers: &Self::Input) -> Self::PartOne {
+        sum_all(numbers).map_or(0, |sum| sum.magnitude())
+    }
+    fn part_two(&self, numbers: &Self::Input) -> Self::PartTwo {
+        largest_pair_magnitude(numbers)
+    }
+    fn describe_part_one(&self, magnitude: &Self::PartOne) -> String {
+        format!("Part One: Magnitude of the final sum: {}", magnitude)
+    }
+    fn describe_part_two(&self, magnitude: &Self::PartTwo) -> String {
+        format!(
+            "Part Two: Largest magnitude of a sum of two numbers: {}",
+            magnitude
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+    fn number(s: &str) -> SnailfishNumber {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_explode() {
+        let explosions = [
+            ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+            ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+            (
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            ),
+            (
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[7,0]]]]",
+            ),
+        ];
+        for (before, after) in explosions {
+            let mut n = number(before);
+            assert_eq!(before, n.to_string());
+            assert!(n.explode());
+            assert_eq!(after, n.to_string());
+        }
+        assert!(!number("[[1,2],[[3,4],5]]").explode());
+    }
+
+    #[test]
+    fn test_reduce_step() {
+        let mut n = number("[[[[4,3],4],4],[7,[[8,4],9]]]") + number("[1,1]");
+        assert_eq!("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", n.to_string());
+        n = number("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]");
+        for expected in [
+            "[[[[0,7],4],[7,[[8,4],9]]],[1,1]]",
+            "[[[[0,7],4],[15,[0,13]]],[1,1]]",
+            "[[[[0,7],4],[[7,8],[0,13]]],[1,1]]",
+            "[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]",
+            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]",
+        ] {
+            assert!(n.reduce_step());
+            assert_eq!(expected, n.to_string());
+        }
+        assert!(!n.reduce_step());
+    }
+
+    #[test]
+    fn test_magnitude() {
+        let magnitudes = [
+            ("[[1,2],[[3,4],5]]", 143),
+            ("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", 1384),
+            ("[[[[1,1],[2,2]],[3,3]],[4,4]]", 445),
+            ("[[[[3,0],[5,3]],[4,4]],[5,5]]", 791),
+            ("[[[[5,0],[7,4]],[5,5]],[6,6]]", 1137),
+            (
+                "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
+                3488,
+            ),
+        ];
+        for (n, magnitude) in magnitudes {
+            assert_eq!(magnitude, number(n).magnitude());
+        }
+    }
+
+    #[test]
+    fn test_part_one() {
+        let numbers = parse_input("[1,1]\n[2,2]\n[3,3]\n[4,4]\n[5,5]\n[6,6]").unwrap();
+        assert_eq!(
+            "[[[[5,0],[7,4]],[5,5]],[6,6]]",
+            sum_all(&numbers).unwrap().to_string()
+        );
+        let numbers = parse_input(TEST_INPUT).unwrap();
+        let sum = sum_all(&numbers).unwrap();
+        assert_eq!(
+            "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]",
+            sum.to_string()
+        );
+        assert_eq!(4140, Puzz18.part_one(&numbers));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let numbers = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(3993, largest_pair_magnitude(&numbers));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((2, 3)),
+            parse_input("[1,2]\n[3]").unwrap_err().location()
+        );
+        assert_eq!(Some((1, 1)), parse_input("7").unwrap_err().location());
+        assert_eq!(Some((1, 6)), parse_input("[1,2]]").unwrap_err().location());
+        assert_eq!(
+            Some((3, 3)),
+            parse_input("[1,2]\n[3,4]\n[[,1],2]")
+                .unwrap_err()
+                .location()
+        );
+    }
+}
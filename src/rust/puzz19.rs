@@ -0,0 +1,552 @@
+//! Day 19: Beacon Scanner.
+
+use std::collections::BTreeSet;
+use std::ops::{Add, Sub};
+
+use crate::error::AocError;
+use crate::input::{self, Line};
+use crate::solver::Solver;
+
+static INPUT_FILE: &str = "puzz19.txt";
+
+/// Beacons two scanners must have in common to be aligned with each other.
+const MIN_OVERLAP: usize = 12;
+
+/// A point in space, or the offset between two points.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point3 {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Point3 { x, y, z }
+    }
+    pub fn manhattan_distance(&self, other: Point3) -> i32 {
+        let d = *self - other;
+        d.x.abs() + d.y.abs() + d.z.abs()
+    }
+    /// Squared Euclidean distance, which every rotation preserves.
+    pub fn distance_squared(&self, other: Point3) -> i64 {
+        let d = *self - other;
+        [d.x, d.y, d.z].iter().map(|&c| (c as i64).pow(2)).sum()
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+/// One of the 24 ways a scanner can be oriented: a rotation by multiples of
+/// 90 degrees about the axes, stored as its matrix.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rotation {
+    matrix: [[i32; 3]; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        matrix: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+    };
+
+    /// Every orientation: each axis can face along any of the six directions
+    /// and the matrix must have determinant 1, ruling out reflections.
+    pub fn all() -> Vec<Rotation> {
+        let permutations = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        let mut rotations = Vec::with_capacity(24);
+        for permutation in permutations {
+            for signs in 0..8 {
+                let mut matrix = [[0; 3]; 3];
+                for (row, &column) in permutation.iter().enumerate() {
+                    matrix[row][column] = if signs & (1 << row) == 0 { 1 } else { -1 };
+                }
+                let rotation = Rotation { matrix };
+                if rotation.determinant() == 1 {
+                    rotations.push(rotation);
+                }
+            }
+        }
+        rotations
+    }
+    fn determinant(&self) -> i32 {
+        let m = &self.matrix;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+    pub fn apply(&self, p: Point3) -> Point3 {
+        let [x, y, z] = self
+            .matrix
+            .map(|row| row[0] * p.x + row[1] * p.y + row[2] * p.z);
+        Point3::new(x, y, z)
+    }
+}
+
+/// The beacons one scanner detected, relative to itself and in its own
+/// orientation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scanner {
+    pub beacons: Vec<Point3>,
+}
+
+fn parse_beacon(line: &Line) -> Result<Point3, AocError> {
+    match line.comma_list::<i32>()?.as_slice() {
+        &[x, y, z] => Ok(Point3::new(x, y, z)),
+        _ => Err(line.error(line.text, "Expected `x,y,z`")),
+    }
+}
+
+/// Parse each scanner's `--- scanner N ---` header and its `x,y,z` beacons,
+/// with scanners separated by blank lines and numbered from 0.
+pub fn parse_input(raw: &str) -> Result<Vec<Scanner>, AocError> {
+    let blocks = input::blocks(raw);
+    if blocks.is_empty() {
+        return Err(AocError::invalid("Empty input"));
+    }
+    let mut scanners = Vec::with_capacity(blocks.len());
+    for (idx, block) in blocks.iter().enumerate() {
+        let header = &block[0];
+        let number = header
+            .text
+            .strip_prefix("--- scanner ")
+            .and_then(|rest| rest.strip_suffix(" ---"))
+            .ok_or_else(|| header.error(header.text, "Expected `--- scanner N ---`"))?;
+        if header.parse_token::<usize>(number)? != idx {
+            return Err(header.error(number, format!("Expected scanner {}", idx)));
+        }
+        let beacons = block[1..]
+            .iter()
+            .map(parse_beacon)
+            .collect::<Result<Vec<Point3>, AocError>>()?;
+        scanners.push(Scanner { beacons });
+    }
+    Ok(scanners)
+}
+
+/// Size of the intersection of two sorted lists, counting repeats.
+fn common(a: &[i64], b: &[i64]) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                count += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    count
+}
+
+/// Distances between a scanner's beacons, which do not depend on where the
+/// scanner is or which way it faces.
+struct Fingerprint {
+    /// Sorted squared distances from each beacon to the scanner's others.
+    beacons: Vec<Vec<i64>>,
+    /// Every pairwise squared distance, sorted.
+    all: Vec<i64>,
+}
+
+impl Fingerprint {
+    fn new(beacons: &[Point3]) -> Self {
+        let mut all = Vec::new();
+        let beacons = beacons
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                let mut distances = beacons
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, &b)| a.distance_squared(b))
+                    .collect::<Vec<i64>>();
+                all.extend(distances[i..].iter().copied());
+                distances.sort_unstable();
+                distances
+            })
+            .collect();
+        all.sort_unstable();
+        Fingerprint { beacons, all }
+    }
+}
+
+/// The rotation and offset that map `beacons` onto at least
+/// [`MIN_OVERLAP`] of the `placed` beacons, if there are any.
+///
+/// Only beacons whose distances to the others largely agree are paired up,
+/// so just a handful of candidate transformations are ever checked.
+fn align_pair(
+    placed: (&[Point3], &Fingerprint),
+    beacons: (&[Point3], &Fingerprint),
+    rotations: &[Rotation],
+) -> Option<(Rotation, Point3)> {
+    let (placed, placed_fingerprint) = placed;
+    let (beacons, fingerprint) = beacons;
+    if common(&placed_fingerprint.all, &fingerprint.all) < MIN_OVERLAP * (MIN_OVERLAP - 1) / 2 {
+        return None;
+    }
+    let pairs = placed
+        .iter()
+        .zip(&placed_fingerprint.beacons)
+        .filter_map(|(&a, a_distances)| {
+            beacons
+                .iter()
+                .zip(&fingerprint.beacons)
+                .find(|(_, b_distances)| common(a_distances, b_distances) >= MIN_OVERLAP - 1)
+                .map(|(&b, _)| (a, b))
+        })
+        .collect::<Vec<(Point3, Point3)>>();
+    if pairs.len() < MIN_OVERLAP {
+        return None;
+    }
+    pairs.iter().find_map(|&(anchor_a, anchor_b)| {
+        rotations.iter().find_map(|&rotation| {
+            let offset = anchor_a - rotation.apply(anchor_b);
+            let matched = pairs
+                .iter()
+                .filter(|&&(a, b)| rotation.apply(b) + offset == a)
+                .count();
+            (matched >= MIN_OVERLAP).then_some((rotation, offset))
+        })
+    })
+}
+
+/// Every beacon and scanner position, relative to and in the orientation of
+/// scanner 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeaconMap {
+    /// Distinct beacons, sorted.
+    pub beacons: Vec<Point3>,
+    pub scanners: Vec<Point3>,
+}
+
+impl BeaconMap {
+    pub fn largest_scanner_distance(&self) -> i32 {
+        self.scanners
+            .iter()
+            .flat_map(|a| self.scanners.iter().map(|&b| a.manhattan_distance(b)))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Align every scanner with scanner 0, through a chain of overlapping
+/// scanners where necessary.
+pub fn align_scanners(scanners: &[Scanner]) -> Result<BeaconMap, AocError> {
+    let rotations = Rotation::all();
+    let fingerprints = scanners
+        .iter()
+        .map(|s| Fingerprint::new(&s.beacons))
+        .collect::<Vec<Fingerprint>>();
+    let mut positions = vec![None; scanners.len()];
+    let mut absolute = vec![Vec::new(); scanners.len()];
+    positions[0] = Some(Point3::default());
+    absolute[0] = scanners[0].beacons.clone();
+    let mut queue = vec![0];
+    while let Some(i) = queue.pop() {
+        for j in 0..scanners.len() {
+            if positions[j].is_some() {
+                continue;
+            }
+            let placed = (absolute[i].as_slice(), &fingerprints[i]);
+            let beacons = (scanners[j].beacons.as_slice(), &fingerprints[j]);
+            if let Some((rotation, offset)) = align_pair(placed, beacons, &rotations) {
+                absolute[j] = scanners[j]
+                    .beacons
+                    .iter()
+                    .map(|&b| rotation.apply(b) + offset)
+                    .collect();
+                positions[j] = Some(offset);
+                queue.push(j);
+            }
+        }
+    }
+    let scanners = positions
+        .iter()
+        .enumerate()
+        .map(|(idx, position)| {
+            position.ok_or_else(|| {
+                AocError::invalid(format!(
+                    "Scanner {} shares too few beacons to be aligned",
+                    idx
+                ))
+            })
+        })
+        .collect::<Result<Vec<Point3>, AocError>>()?;
+    let beacons = absolute
+        .into_iter()
+        .flatten()
+        .collect::<BTreeSet<Point3>>()
+        .into_iter()
+        .collect();
+    Ok(BeaconMap { beacons, scanners })
+}
+
+/// [`Solver`] for day 19.
+pub struct Puzz19;
+
+impl Solver for Puzz19 {
+    const DAY: usize = 19;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = BeaconMap;
+    type PartOne = usize;
+    type PartTwo = i32;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        align_scanners(&parse_input(raw)?)
+    }
+    fn part_one(&self, map: &Self::Input) -> Self::PartOne {
+        map.beacons.len()
+    }
+    fn part_two(&self, map: &Self::Input) -> Self::PartTwo {
+        map.largest_scanner_distance()
+    }
+    fn describe_part_one(&self, num_beacons: &Self::PartOne) -> String {
+        format!("Part One: Number of distinct beacons: {}", num_beacons)
+    }
+    fn describe_part_two(&self, distance: &Self::PartTwo) -> String {
+        format!(
+            "Part Two: Largest Manhattan distance between scanners: {}",
+            distance
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str = "--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14";
+
+    #[test]
+    fn test_rotations() {
+        let rotations = Rotation::all();
+        assert_eq!(24, rotations.len());
+        assert!(rotations.contains(&Rotation::IDENTITY));
+        let p = Point3::new(1, 2, 3);
+        let images = rotations
+            .iter()
+            .map(|r| r.apply(p))
+            .collect::<BTreeSet<Point3>>();
+        assert_eq!(24, images.len());
+        assert!(images
+            .iter()
+            .all(|q| q.distance_squared(Point3::default()) == 14));
+        let seen = |raw: &str| parse_input(raw).unwrap().remove(0).beacons;
+        let facing = seen("--- scanner 0 ---\n-1,-1,1\n-2,-2,2\n-3,-3,3\n-2,-3,1\n5,6,-4\n8,0,7");
+        let turned = seen("--- scanner 0 ---\n1,-1,1\n2,-2,2\n3,-3,3\n2,-1,3\n-5,4,-6\n-8,-7,0");
+        assert!(rotations.iter().any(|r| facing
+            .iter()
+            .map(|&b| r.apply(b))
+            .eq(turned.iter().copied())));
+    }
+
+    #[test]
+    fn test_part_one() {
+        let map = align_scanners(&parse_input(TEST_INPUT).unwrap()).unwrap();
+        assert_eq!(
+            vec![
+                Point3::new(0, 0, 0),
+                Point3::new(68, -1246, -43),
+                Point3::new(1105, -1205, 1229),
+                Point3::new(-92, -2380, -20),
+                Point3::new(-20, -1133, 1061),
+            ],
+            map.scanners
+        );
+        assert_eq!(79, map.beacons.len());
+    }
+
+    #[test]
+    fn test_part_two() {
+        let map = align_scanners(&parse_input(TEST_INPUT).unwrap()).unwrap();
+        assert_eq!(3621, map.largest_scanner_distance());
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((1, 13)),
+            parse_input("--- scanner 1 ---\n1,2,3")
+                .unwrap_err()
+                .location()
+        );
+        assert_eq!(
+            Some((3, 1)),
+            parse_input("--- scanner 0 ---\n1,2,3\n4,5")
+                .unwrap_err()
+                .location()
+        );
+        assert_eq!(
+            Some((2, 3)),
+            parse_input("--- scanner 0 ---\n1,y,3")
+                .unwrap_err()
+                .location()
+        );
+        let unaligned = "--- scanner 0 ---\n1,2,3\n\n--- scanner 1 ---\n4,5,6";
+        assert!(matches!(
+            align_scanners(&parse_input(unaligned).unwrap()),
+            Err(AocError::InvalidData(_))
+        ));
+        assert!(matches!(
+            Puzz19.parse(unaligned),
+            Err(AocError::InvalidData(_))
+        ));
+    }
+}
@@ -1,60 +1,72 @@
-use std::error::Error;
-use std::path::Path;
-
-static INPUT_PATH: &str = "input/puzz2.csv";
-
-fn get_input(path: &Path) -> Result<Vec<Movement>, Box<dyn Error>> {
-    let mut rdr = csv::ReaderBuilder::new()
-        .has_headers(false)
-        .from_path(&path)?;
-    let mut input: Vec<Movement> = Vec::new();
-    for result in rdr.records() {
-        input.push(result?.as_slice().try_into()?)
-    }
-    Ok(input)
+//! Day 2: Dive!
+
+use std::fmt;
+
+use crate::error::{column_of, parse_token, AocError};
+use crate::input;
+use crate::solver::{Answer, Solver};
+
+static INPUT_FILE: &str = "puzz2.csv";
+
+/// Parse one `direction distance` movement per line.
+pub fn parse_input(raw: &str) -> Result<Vec<Movement>, AocError> {
+    input::parse_lines(raw, |line| {
+        Movement::try_from(line.text).map_err(|e| e.at_line(line.number))
+    })
 }
 
+/// A single step of the planned course.
 #[derive(Debug, Clone, Copy)]
-enum Movement {
+pub enum Movement {
     Forward(i32),
     Down(i32),
     Up(i32),
 }
 
 impl TryFrom<&str> for Movement {
-    type Error = Box<dyn Error>;
+    type Error = AocError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let pair = value.split(' ').collect::<Vec<&str>>();
         if pair.len() != 2 {
-            return Err(String::from("Failed to parse movement pair").into());
+            return Err(AocError::parse(
+                1,
+                1,
+                format!("Failed to parse {:?} as a movement pair", value),
+            ));
         }
         let (direction, distance) = (pair[0], pair[1]);
-        let distance = distance.parse::<i32>()?;
+        let distance = parse_token::<i32>(distance, value, 1)?;
         match direction {
             "forward" => Ok(Movement::Forward(distance)),
             "down" => Ok(Movement::Down(distance)),
             "up" => Ok(Movement::Up(distance)),
-            _ => Err(format!("Failed to parse {} as a direction", direction).into()),
+            _ => Err(AocError::parse(
+                1,
+                column_of(value, direction),
+                format!("Failed to parse {:?} as a direction", direction),
+            )),
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct SubPosition {
-    aim: i32,
-    horizontal: i32,
-    depth: i32,
+/// Position of the submarine; its `Display` is horizontal times depth.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct SubPosition {
+    pub aim: i32,
+    pub horizontal: i32,
+    pub depth: i32,
 }
 
 impl SubPosition {
-    fn new() -> Self {
+    pub fn new() -> Self {
         SubPosition {
             aim: 0,
             horizontal: 0,
             depth: 0,
         }
     }
-    fn single_move_p1(&mut self, movement: Movement) {
+    /// Apply a movement as the part one course interprets it.
+    pub fn single_move_p1(&mut self, movement: Movement) {
         match movement {
             Movement::Forward(v) => {
                 self.horizontal += v;
@@ -67,12 +79,13 @@ impl SubPosition {
             }
         }
     }
-    fn travel_course_p1(&mut self, movements: &[Movement]) {
+    pub fn travel_course_p1(&mut self, movements: &[Movement]) {
         for &m in movements {
             self.single_move_p1(m)
         }
     }
-    fn single_move_p2(&mut self, movement: Movement) {
+    /// Apply a movement as the part two course interprets it, steering by aim.
+    pub fn single_move_p2(&mut self, movement: Movement) {
         match movement {
             Movement::Forward(v) => {
                 self.horizontal += v;
@@ -86,29 +99,64 @@ impl SubPosition {
             }
         }
     }
-    fn travel_course_p2(&mut self, movements: &[Movement]) {
+    pub fn travel_course_p2(&mut self, movements: &[Movement]) {
         for &m in movements {
             self.single_move_p2(m)
         }
     }
 }
 
-pub(crate) fn puzz2() {
-    let movements = get_input(Path::new(INPUT_PATH)).expect("Could not read input data");
-    let mut sub_position = SubPosition::new();
-    sub_position.travel_course_p1(&movements);
-    let multiplication = sub_position.horizontal * sub_position.depth;
-    println!(
-        "Part one course: Final (horizontal, depth) positions: ({}, {}); multiplication: {}",
-        sub_position.horizontal, sub_position.depth, multiplication
-    );
-    let mut sub_position = SubPosition::new();
-    sub_position.travel_course_p2(&movements);
-    let multiplication = sub_position.horizontal * sub_position.depth;
-    println!(
-        "Part two course: Final (horizontal, depth) positions: ({}, {}); multiplication: {}",
-        sub_position.horizontal, sub_position.depth, multiplication
-    );
+impl fmt::Display for SubPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.horizontal * self.depth)
+    }
+}
+
+impl Answer for SubPosition {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("horizontal", self.horizontal.to_string()),
+            ("depth", self.depth.to_string()),
+        ]
+    }
+}
+
+/// [`Solver`] for day 2.
+pub struct Puzz2;
+
+impl Solver for Puzz2 {
+    const DAY: usize = 2;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Vec<Movement>;
+    type PartOne = SubPosition;
+    type PartTwo = SubPosition;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, movements: &Self::Input) -> Self::PartOne {
+        let mut sub_position = SubPosition::new();
+        sub_position.travel_course_p1(movements);
+        sub_position
+    }
+    fn part_two(&self, movements: &Self::Input) -> Self::PartTwo {
+        let mut sub_position = SubPosition::new();
+        sub_position.travel_course_p2(movements);
+        sub_position
+    }
+    fn describe_part_one(&self, sub_position: &Self::PartOne) -> String {
+        format!(
+            "Part one course: Final (horizontal, depth) positions: ({}, {}); multiplication: {}",
+            sub_position.horizontal, sub_position.depth, sub_position
+        )
+    }
+    fn describe_part_two(&self, sub_position: &Self::PartTwo) -> String {
+        format!(
+            "Part two course: Final (horizontal, depth) positions: ({}, {}); multiplication: {}",
+            sub_position.horizontal, sub_position.depth, sub_position
+        )
+    }
 }
 
 #[cfg(test)]
@@ -128,10 +176,9 @@ mod tests {
         let movements = TEST_INPUT
             .iter()
             .map(|&s| Movement::try_from(s))
-            .collect::<Result<Vec<Movement>, Box<dyn Error>>>()
+            .collect::<Result<Vec<Movement>, AocError>>()
             .unwrap();
-        let mut sub_position = SubPosition::new();
-        sub_position.travel_course_p1(&movements);
+        let sub_position = Puzz2.part_one(&movements);
         assert_eq!(150, sub_position.horizontal * sub_position.depth);
     }
 
@@ -140,10 +187,9 @@ mod tests {
         let movements = TEST_INPUT
             .iter()
             .map(|&s| Movement::try_from(s))
-            .collect::<Result<Vec<Movement>, Box<dyn Error>>>()
+            .collect::<Result<Vec<Movement>, AocError>>()
             .unwrap();
-        let mut sub_position = SubPosition::new();
-        sub_position.travel_course_p2(&movements);
+        let sub_position = Puzz2.part_two(&movements);
         assert_eq!(900, sub_position.horizontal * sub_position.depth);
     }
 }
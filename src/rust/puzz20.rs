@@ -0,0 +1,228 @@
+//! Day 20: Trench Map.
+
+use std::fmt;
+
+use crate::error::AocError;
+use crate::input::{self, Grid};
+use crate::puzz3::bits_to_integer;
+use crate::solver::Solver;
+
+static INPUT_FILE: &str = "puzz20.txt";
+
+/// Number of entries in the image enhancement algorithm, one for every 3x3
+/// neighbourhood.
+const ALGORITHM_LEN: usize = 512;
+
+fn pixel(c: char) -> Option<u8> {
+    match c {
+        '#' => Some(1),
+        '.' => Some(0),
+        _ => None,
+    }
+}
+
+/// An image on an infinite canvas: the pixels of a finite region, and the
+/// state every pixel outside it shares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub pixels: Grid<u8>,
+    pub background: u8,
+}
+
+impl Image {
+    /// The pixel at `(x, y)` relative to the top left of the finite region,
+    /// which may be anywhere on the canvas.
+    fn pixel(&self, x: isize, y: isize) -> u8 {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) => self.pixels.get(x, y).copied().unwrap_or(self.background),
+            _ => self.background,
+        }
+    }
+    /// Apply the enhancement `algorithm` once; the finite region grows by one
+    /// pixel on every side, and the background is enhanced like any other
+    /// pixel surrounded by background.
+    pub fn enhance(&self, algorithm: &[u8]) -> Image {
+        let (width, height) = (self.pixels.width + 2, self.pixels.height + 2);
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height as isize {
+            for x in 0..width as isize {
+                let mut neighbourhood = [0; 9];
+                for (idx, bit) in neighbourhood.iter_mut().enumerate() {
+                    let (dx, dy) = (idx as isize % 3, idx as isize / 3);
+                    *bit = self.pixel(x + dx - 2, y + dy - 2);
+                }
+                cells.push(algorithm[bits_to_integer(&neighbourhood)]);
+            }
+        }
+        let background = algorithm[bits_to_integer(&[self.background; 9])];
+        Image {
+            pixels: Grid {
+                width,
+                height,
+                cells,
+            },
+            background,
+        }
+    }
+    /// Number of lit pixels, or `None` if infinitely many are lit.
+    pub fn lit_pixels(&self) -> Option<usize> {
+        (self.background == 0).then(|| self.pixels.cells.iter().filter(|&&p| p == 1).count())
+    }
+}
+
+/// The finite region drawn with `#` for lit pixels and `.` for dark ones.
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (ridx, row) in self.pixels.rows().enumerate() {
+            if ridx > 0 {
+                writeln!(f)?;
+            }
+            for &p in row {
+                write!(f, "{}", if p == 1 { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The image enhancement algorithm and the input image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrenchMap {
+    pub algorithm: Vec<u8>,
+    pub image: Image,
+}
+
+/// Parse the algorithm line and then, after a blank line, the image.
+///
+/// An algorithm that lights every pixel of an all-dark neighbourhood and
+/// keeps every pixel of an all-lit one lit would leave infinitely many
+/// pixels lit after any number of passes, so it is rejected.
+pub fn parse_input(raw: &str) -> Result<TrenchMap, AocError> {
+    let blocks = input::blocks(raw);
+    let (algorithm_line, image) = match blocks.as_slice() {
+        [algorithm, image] if algorithm.len() == 1 => (algorithm[0], image),
+        _ => {
+            return Err(AocError::invalid(
+                "Expected an algorithm line and an image separated by a blank line",
+            ))
+        }
+    };
+    let algorithm = input::grid_from_lines(&[algorithm_line], pixel)?.cells;
+    if algorithm.len() != ALGORITHM_LEN {
+        return Err(algorithm_line.error(
+            algorithm_line.text,
+            format!(
+                "Expected an algorithm of {} pixels, found {}",
+                ALGORITHM_LEN,
+                algorithm.len()
+            ),
+        ));
+    }
+    if algorithm[0] == 1 && algorithm[ALGORITHM_LEN - 1] == 1 {
+        return Err(algorithm_line.error(
+            algorithm_line.text,
+            "The algorithm lights the infinite background for good",
+        ));
+    }
+    let image = Image {
+        pixels: input::grid_from_lines(image, pixel)?,
+        background: 0,
+    };
+    Ok(TrenchMap { algorithm, image })
+}
+
+/// Number of lit pixels after `num_passes` of enhancement, which must be
+/// even so that the background is dark.
+pub fn lit_after(map: &TrenchMap, num_passes: usize) -> usize {
+    let image = (0..num_passes).fold(map.image.clone(), |image, _| image.enhance(&map.algorithm));
+    image
+        .lit_pixels()
+        .expect("The background is dark after an even number of passes")
+}
+
+/// [`Solver`] for day 20.
+pub struct Puzz20;
+
+impl Solver for Puzz20 {
+    const DAY: usize = 20;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = TrenchMap;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, map: &Self::Input) -> Self::PartOne {
+        lit_after(map, 2)
+    }
+    fn part_two(&self, map: &Self::Input) -> Self::PartTwo {
+        lit_after(map, 50)
+    }
+    fn describe_part_one(&self, num_lit: &Self::PartOne) -> String {
+        format!("Part One: Lit pixels after 2 enhancements: {}", num_lit)
+    }
+    fn describe_part_two(&self, num_lit: &Self::PartTwo) -> String {
+        format!("Part Two: Lit pixels after 50 enhancements: {}", num_lit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+    #[test]
+    fn test_part_one() {
+        let map = parse_input(TEST_INPUT).unwrap();
+        assert_eq!("#..#.\n#....\n##..#\n..#..\n..###", map.image.to_string());
+        let once = map.image.enhance(&map.algorithm);
+        assert_eq!(
+            ".##.##.\n#..#.#.\n##.#..#\n####..#\n.#..##.\n..##..#\n...#.#.",
+            once.to_string()
+        );
+        assert_eq!(35, lit_after(&map, 2));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let map = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(3351, lit_after(&map, 50));
+    }
+
+    #[test]
+    fn test_flipping_background() {
+        let mut algorithm = vec![0; ALGORITHM_LEN];
+        algorithm[0] = 1;
+        let image = Image {
+            pixels: input::grid("#", pixel).unwrap(),
+            background: 0,
+        };
+        let once = image.enhance(&algorithm);
+        assert_eq!((1, None), (once.background, once.lit_pixels()));
+        assert_eq!("...\n...\n...", once.to_string());
+        let twice = once.enhance(&algorithm);
+        assert_eq!((0, Some(1)), (twice.background, twice.lit_pixels()));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((1, 1)),
+            parse_input("#.#\n\n#.").unwrap_err().location()
+        );
+        let algorithm = TEST_INPUT.lines().next().unwrap();
+        let err = parse_input(&format!("{}\n\n#x\n..", algorithm)).unwrap_err();
+        assert_eq!(Some((3, 2)), err.location());
+        let always_lit = format!("#{}#", &algorithm[1..ALGORITHM_LEN - 1]);
+        let err = parse_input(&format!("{}\n\n#.", always_lit)).unwrap_err();
+        assert_eq!(Some((1, 1)), err.location());
+    }
+}
@@ -0,0 +1,302 @@
+//! Day 21: Dirac Dice.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::error::AocError;
+use crate::input;
+use crate::solver::{Answer, Solver};
+
+static INPUT_FILE: &str = "puzz21.txt";
+
+/// Times each player rolls the die on their turn.
+const ROLLS_PER_TURN: usize = 3;
+
+/// The board, target and die a game is played with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GameRules {
+    /// Spaces on the circular track, numbered from 1.
+    pub board_size: u32,
+    /// Score at which a player wins immediately.
+    pub winning_score: u32,
+    /// Sides of the die, numbered from 1.
+    pub die_faces: u32,
+}
+
+impl GameRules {
+    /// The space reached by moving `steps` forward from `position`.
+    pub fn advance(&self, position: u32, steps: u32) -> u32 {
+        (position - 1 + steps) % self.board_size + 1
+    }
+}
+
+/// The practice game with a deterministic 100-sided die.
+pub const PRACTICE_GAME: GameRules = GameRules {
+    board_size: 10,
+    winning_score: 1000,
+    die_faces: 100,
+};
+
+/// The real game with the 3-sided Dirac die.
+pub const DIRAC_GAME: GameRules = GameRules {
+    board_size: 10,
+    winning_score: 21,
+    die_faces: 3,
+};
+
+/// Parse the `Player N starting position: P` line of both players.
+pub fn parse_input(raw: &str) -> Result<[u32; 2], AocError> {
+    let mut positions = Vec::with_capacity(2);
+    input::parse_lines(raw, |line| {
+        let expected = format!("Player {} starting position: ", positions.len() + 1);
+        let position = line
+            .text
+            .strip_prefix(expected.as_str())
+            .ok_or_else(|| line.error(line.text, format!("Expected `{}P`", expected)))?;
+        match line.parse_token::<u32>(position)? {
+            p @ 1..=10 => positions.push(p),
+            _ => return Err(line.error(position, "Positions are from 1 to 10")),
+        }
+        Ok(())
+    })?;
+    positions.try_into().map_err(|positions: Vec<u32>| {
+        AocError::invalid(format!("Expected 2 players, found {}", positions.len()))
+    })
+}
+
+/// The end of a practice game; its `Display` is the losing score times the
+/// number of rolls.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PracticeOutcome {
+    pub losing_score: u32,
+    pub num_rolls: u32,
+}
+
+impl fmt::Display for PracticeOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.losing_score * self.num_rolls)
+    }
+}
+
+impl Answer for PracticeOutcome {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("losing_score", self.losing_score.to_string()),
+            ("num_rolls", self.num_rolls.to_string()),
+        ]
+    }
+}
+
+/// Play with a deterministic die that rolls 1, 2, 3 and so on, wrapping
+/// back to 1 after its last face.
+pub fn play_deterministic(start: [u32; 2], rules: &GameRules) -> PracticeOutcome {
+    let (mut positions, mut scores) = (start, [0, 0]);
+    let mut num_rolls = 0;
+    let mut roll = || {
+        num_rolls += 1;
+        (num_rolls - 1) % rules.die_faces + 1
+    };
+    for turn in (0..2).cycle() {
+        let steps = (0..ROLLS_PER_TURN).map(|_| roll()).sum();
+        positions[turn] = rules.advance(positions[turn], steps);
+        scores[turn] += positions[turn];
+        if scores[turn] >= rules.winning_score {
+            break;
+        }
+    }
+    PracticeOutcome {
+        losing_score: scores[0].min(scores[1]),
+        num_rolls,
+    }
+}
+
+/// Universes in which each player wins; its `Display` is the larger count.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UniverseWins {
+    pub wins: [u64; 2],
+}
+
+impl fmt::Display for UniverseWins {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.wins[0].max(self.wins[1]))
+    }
+}
+
+impl Answer for UniverseWins {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("player_1", self.wins[0].to_string()),
+            ("player_2", self.wins[1].to_string()),
+        ]
+    }
+}
+
+/// Positions, scores and whose turn it is.
+type GameState = ([u32; 2], [u32; 2], usize);
+
+struct QuantumCounter<'a> {
+    rules: &'a GameRules,
+    /// Every total of a turn's rolls and the number of universes rolling it.
+    turn_totals: Vec<(u32, u64)>,
+    memo: HashMap<GameState, [u64; 2]>,
+}
+
+impl QuantumCounter<'_> {
+    fn wins(&mut self, (positions, scores, turn): GameState) -> [u64; 2] {
+        if let Some(&wins) = self.memo.get(&(positions, scores, turn)) {
+            return wins;
+        }
+        let mut wins = [0, 0];
+        for idx in 0..self.turn_totals.len() {
+            let (total, universes) = self.turn_totals[idx];
+            let (mut positions, mut scores) = (positions, scores);
+            positions[turn] = self.rules.advance(positions[turn], total);
+            scores[turn] += positions[turn];
+            if scores[turn] >= self.rules.winning_score {
+                wins[turn] += universes;
+            } else {
+                let later = self.wins((positions, scores, 1 - turn));
+                wins[0] += universes * later[0];
+                wins[1] += universes * later[1];
+            }
+        }
+        self.memo.insert((positions, scores, turn), wins);
+        wins
+    }
+}
+
+/// Count the universes each player wins in when every roll of the die splits
+/// the universe once per face; player 1 moves first.
+pub fn count_quantum_wins(start: [u32; 2], rules: &GameRules) -> UniverseWins {
+    let mut universes = vec![0; ROLLS_PER_TURN * rules.die_faces as usize + 1];
+    universes[0] = 1;
+    for _ in 0..ROLLS_PER_TURN {
+        let mut next = vec![0; universes.len()];
+        for (total, &count) in universes.iter().enumerate().filter(|(_, &c)| c > 0) {
+            for face in 1..=rules.die_faces as usize {
+                next[total + face] += count;
+            }
+        }
+        universes = next;
+    }
+    let turn_totals = universes
+        .iter()
+        .enumerate()
+        .filter(|(_, &count)| count > 0)
+        .map(|(total, &count)| (total as u32, count))
+        .collect();
+    let mut counter = QuantumCounter {
+        rules,
+        turn_totals,
+        memo: HashMap::new(),
+    };
+    UniverseWins {
+        wins: counter.wins((start, [0, 0], 0)),
+    }
+}
+
+/// [`Solver`] for day 21.
+pub struct Puzz21;
+
+impl Solver for Puzz21 {
+    const DAY: usize = 21;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = [u32; 2];
+    type PartOne = PracticeOutcome;
+    type PartTwo = UniverseWins;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, start: &Self::Input) -> Self::PartOne {
+        play_deterministic(*start, &PRACTICE_GAME)
+    }
+    fn part_two(&self, start: &Self::Input) -> Self::PartTwo {
+        count_quantum_wins(*start, &DIRAC_GAME)
+    }
+    fn describe_part_one(&self, outcome: &Self::PartOne) -> String {
+        format!(
+            "Part One: Losing score {} after {} rolls; multiplication: {}",
+            outcome.losing_score, outcome.num_rolls, outcome
+        )
+    }
+    fn describe_part_two(&self, wins: &Self::PartTwo) -> String {
+        format!(
+            "Part Two: Universes won by player 1: {}, player 2: {}; most: {}",
+            wins.wins[0], wins.wins[1], wins
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str = "Player 1 starting position: 4
+Player 2 starting position: 8";
+
+    #[test]
+    fn test_part_one() {
+        let start = parse_input(TEST_INPUT).unwrap();
+        assert_eq!([4, 8], start);
+        let outcome = play_deterministic(start, &PRACTICE_GAME);
+        assert_eq!(
+            PracticeOutcome {
+                losing_score: 745,
+                num_rolls: 993
+            },
+            outcome
+        );
+        assert_eq!("739785", outcome.to_string());
+    }
+
+    #[test]
+    fn test_part_two() {
+        let start = parse_input(TEST_INPUT).unwrap();
+        let wins = count_quantum_wins(start, &DIRAC_GAME);
+        assert_eq!([444356092776315, 341960390180808], wins.wins);
+        assert_eq!("444356092776315", wins.to_string());
+    }
+
+    #[test]
+    fn test_rules() {
+        let first_turn = GameRules {
+            winning_score: 1,
+            ..DIRAC_GAME
+        };
+        assert_eq!([27, 0], count_quantum_wins([4, 8], &first_turn).wins);
+        let coin = GameRules {
+            die_faces: 2,
+            ..first_turn
+        };
+        assert_eq!([8, 0], count_quantum_wins([4, 8], &coin).wins);
+        let small_board = GameRules {
+            board_size: 3,
+            winning_score: 4,
+            die_faces: 1,
+        };
+        // Each turn moves three spaces, right back to the starting space.
+        let outcome = play_deterministic([1, 2], &small_board);
+        assert_eq!((2, 12), (outcome.losing_score, outcome.num_rolls));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((2, 1)),
+            parse_input("Player 1 starting position: 4\nPlayer 1 starting position: 8")
+                .unwrap_err()
+                .location()
+        );
+        assert_eq!(
+            Some((1, 29)),
+            parse_input("Player 1 starting position: 11")
+                .unwrap_err()
+                .location()
+        );
+        assert!(matches!(
+            parse_input("Player 1 starting position: 4"),
+            Err(AocError::InvalidData(_))
+        ));
+    }
+}
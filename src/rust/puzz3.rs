@@ -1,17 +1,22 @@
-use std::error::Error;
-use std::path::Path;
+//! Day 3: Binary Diagnostic.
 
-static INPUT_PATH: &str = "input/puzz3.csv";
+use std::fmt;
 
-fn get_input(path: &Path) -> Result<DiagnosticData, Box<dyn Error>> {
-    let mut rdr = csv::ReaderBuilder::new()
-        .has_headers(false)
-        .from_path(&path)?;
-    let mut input: Vec<String> = Vec::new();
-    for result in rdr.records() {
-        input.push(result?.as_slice().into())
-    }
-    input.as_slice().try_into()
+use crate::error::AocError;
+use crate::input;
+use crate::solver::{Answer, Solver};
+
+static INPUT_FILE: &str = "puzz3.csv";
+
+/// Parse one binary diagnostic number per line.
+pub fn parse_input(raw: &str) -> Result<DiagnosticData, AocError> {
+    let grid = input::grid(raw, |c| c.to_digit(2).map(|bit| bit as u8))?;
+    Ok(DiagnosticData {
+        data: grid.cells,
+        num_cols: grid.width,
+        num_rows: grid.height,
+        order: ArrayOrder::RowMajor,
+    })
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -26,8 +31,10 @@ enum LifeSupportRating {
     Co2Scrub,
 }
 
+/// The diagnostic report as a dense matrix of bits, stored either row- or
+/// column-major.
 #[derive(Debug, Clone)]
-struct DiagnosticData {
+pub struct DiagnosticData {
     data: Vec<u8>,
     num_cols: usize,
     num_rows: usize,
@@ -35,7 +42,7 @@ struct DiagnosticData {
 }
 
 impl DiagnosticData {
-    fn to_row_major(&self) -> Self {
+    pub fn to_row_major(&self) -> Self {
         let data = match self.order {
             ArrayOrder::RowMajor => self.data.clone(),
             ArrayOrder::ColMajor => {
@@ -55,7 +62,7 @@ impl DiagnosticData {
             order: ArrayOrder::RowMajor,
         }
     }
-    fn to_col_major(&self) -> Self {
+    pub fn to_col_major(&self) -> Self {
         let data = match self.order {
             ArrayOrder::ColMajor => self.data.clone(),
             ArrayOrder::RowMajor => {
@@ -78,17 +85,33 @@ impl DiagnosticData {
 }
 
 impl TryFrom<&[String]> for DiagnosticData {
-    type Error = Box<dyn Error>;
+    type Error = AocError;
     fn try_from(input: &[String]) -> Result<Self, Self::Error> {
         let num_rows = input.len();
-        let num_cols = input[0].len();
+        let num_cols = input.first().map_or(0, |row| row.len());
+        if num_cols == 0 {
+            return Err(AocError::invalid("No diagnostic numbers"));
+        }
         let mut data = Vec::with_capacity(num_rows * num_cols);
-        for row in input.iter() {
-            for &v in row.as_bytes() {
+        for (ridx, row) in input.iter().enumerate() {
+            if row.len() != num_cols {
+                return Err(AocError::parse(
+                    ridx + 1,
+                    1,
+                    format!("Expected {} bits, found {}", num_cols, row.len()),
+                ));
+            }
+            for (cidx, &v) in row.as_bytes().iter().enumerate() {
                 match v {
                     48 => data.push(0),
                     49 => data.push(1),
-                    _ => return Err(format!("Encountered non-0/1 ASCII: {}", v).into()),
+                    _ => {
+                        return Err(AocError::parse(
+                            ridx + 1,
+                            cidx + 1,
+                            format!("Encountered non-0/1 ASCII: {}", v),
+                        ))
+                    }
                 }
             }
         }
@@ -101,7 +124,8 @@ impl TryFrom<&[String]> for DiagnosticData {
     }
 }
 
-fn compute_gamma_bits(data: &DiagnosticData) -> Vec<u8> {
+/// Most common bit in each column of the report.
+pub fn compute_gamma_bits(data: &DiagnosticData) -> Vec<u8> {
     data.to_col_major()
         .data
         .chunks_exact(data.num_rows)
@@ -116,17 +140,15 @@ fn compute_gamma_bits(data: &DiagnosticData) -> Vec<u8> {
         .collect::<Vec<u8>>()
 }
 
-fn bits_to_integer(bits: &[u8]) -> usize {
-    let mut integer = 0;
-    for (idx, &b) in bits.iter().rev().enumerate() {
-        if b > 0 {
-            integer += 1 << idx;
-        }
-    }
-    integer
+/// Interpret most-significant-first 0/1 values as an integer; any non-zero
+/// value counts as a 1.
+pub fn bits_to_integer<'a>(bits: impl IntoIterator<Item = &'a u8>) -> usize {
+    bits.into_iter()
+        .fold(0, |integer, &b| (integer << 1) | usize::from(b > 0))
 }
 
-fn compute_gamma_and_eps_values(gamma_bits: &[u8]) -> (usize, usize) {
+/// Gamma and epsilon rates from the most common bits of each column.
+pub fn compute_gamma_and_eps_values(gamma_bits: &[u8]) -> (usize, usize) {
     let mut gamma_value = 0usize;
     let mut epsilon_value = 0usize;
     for (idx, &v) in gamma_bits.iter().rev().enumerate() {
@@ -149,7 +171,7 @@ fn filter_rows_at_cidx(
     let data_cm = data.to_col_major();
     let col_data = &data_cm.data[start..stop];
     let sum = col_data.iter().map(|&v| v as usize).sum::<usize>();
-    let more_ones = if data.num_rows % 2 == 0 {
+    let more_ones = if data.num_rows.is_multiple_of(2) {
         sum >= data.num_rows / 2
     } else {
         sum > data.num_rows / 2
@@ -188,7 +210,8 @@ fn filter_rows_at_cidx(
     }
 }
 
-fn compute_oxygen_generator_rating(data: &DiagnosticData) -> usize {
+/// Rating left after repeatedly keeping rows with the most common bit.
+pub fn compute_oxygen_generator_rating(data: &DiagnosticData) -> usize {
     let mut filtered_rows = data.to_row_major();
     for cidx in 0..data.num_cols {
         filtered_rows = filter_rows_at_cidx(&filtered_rows, cidx, LifeSupportRating::OxyGen);
@@ -199,7 +222,8 @@ fn compute_oxygen_generator_rating(data: &DiagnosticData) -> usize {
     bits_to_integer(filtered_rows.data.as_slice())
 }
 
-fn compute_co2_scrubber_rating(data: &DiagnosticData) -> usize {
+/// Rating left after repeatedly keeping rows with the least common bit.
+pub fn compute_co2_scrubber_rating(data: &DiagnosticData) -> usize {
     let mut filtered_rows = data.to_row_major();
     for cidx in 0..data.num_cols {
         filtered_rows = filter_rows_at_cidx(&filtered_rows, cidx, LifeSupportRating::Co2Scrub);
@@ -210,30 +234,100 @@ fn compute_co2_scrubber_rating(data: &DiagnosticData) -> usize {
     bits_to_integer(filtered_rows.data.as_slice())
 }
 
-pub(crate) fn puzz3() {
-    let data = get_input(Path::new(INPUT_PATH))
-        .expect("Could not parse input data")
-        .to_col_major();
-    let gamma_bits = compute_gamma_bits(&data);
-    let (gamma_value, epsilon_value) = compute_gamma_and_eps_values(gamma_bits.as_slice());
-    println!(
-        "Part one | (gamma, epsilon) ({}, {}); multiplication: {}",
-        gamma_value,
-        epsilon_value,
-        gamma_value * epsilon_value
-    );
-    let oxygen_generator_rating = compute_oxygen_generator_rating(&data);
-    let co2_scrubber_rating = compute_co2_scrubber_rating(&data);
-    println!(
-        "Part two | oxygen generator rating: {}, CO2 scrubber rating: {}; multiplication: {}",
-        oxygen_generator_rating,
-        co2_scrubber_rating,
-        oxygen_generator_rating * co2_scrubber_rating
-    );
+/// Part one answer; its `Display` is gamma times epsilon.
+#[derive(Debug, Copy, Clone)]
+pub struct PowerConsumption {
+    pub gamma_value: usize,
+    pub epsilon_value: usize,
 }
 
-#[cfg(test)]
+impl fmt::Display for PowerConsumption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.gamma_value * self.epsilon_value)
+    }
+}
+
+impl Answer for PowerConsumption {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("gamma", self.gamma_value.to_string()),
+            ("epsilon", self.epsilon_value.to_string()),
+        ]
+    }
+}
+
+/// Part two answer; its `Display` is the product of both ratings.
+#[derive(Debug, Copy, Clone)]
+pub struct LifeSupportRatings {
+    pub oxygen_generator_rating: usize,
+    pub co2_scrubber_rating: usize,
+}
+
+impl fmt::Display for LifeSupportRatings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.oxygen_generator_rating * self.co2_scrubber_rating
+        )
+    }
+}
+
+impl Answer for LifeSupportRatings {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (
+                "oxygen_generator_rating",
+                self.oxygen_generator_rating.to_string(),
+            ),
+            ("co2_scrubber_rating", self.co2_scrubber_rating.to_string()),
+        ]
+    }
+}
+
+/// [`Solver`] for day 3.
+pub struct Puzz3;
+
+impl Solver for Puzz3 {
+    const DAY: usize = 3;
+    const INPUT_FILE: &'static str = INPUT_FILE;
 
+    type Input = DiagnosticData;
+    type PartOne = PowerConsumption;
+    type PartTwo = LifeSupportRatings;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        Ok(parse_input(raw)?.to_col_major())
+    }
+    fn part_one(&self, data: &Self::Input) -> Self::PartOne {
+        let gamma_bits = compute_gamma_bits(data);
+        let (gamma_value, epsilon_value) = compute_gamma_and_eps_values(gamma_bits.as_slice());
+        PowerConsumption {
+            gamma_value,
+            epsilon_value,
+        }
+    }
+    fn part_two(&self, data: &Self::Input) -> Self::PartTwo {
+        LifeSupportRatings {
+            oxygen_generator_rating: compute_oxygen_generator_rating(data),
+            co2_scrubber_rating: compute_co2_scrubber_rating(data),
+        }
+    }
+    fn describe_part_one(&self, power: &Self::PartOne) -> String {
+        format!(
+            "Part one | (gamma, epsilon) ({}, {}); multiplication: {}",
+            power.gamma_value, power.epsilon_value, power
+        )
+    }
+    fn describe_part_two(&self, ratings: &Self::PartTwo) -> String {
+        format!(
+            "Part two | oxygen generator rating: {}, CO2 scrubber rating: {}; multiplication: {}",
+            ratings.oxygen_generator_rating, ratings.co2_scrubber_rating, ratings
+        )
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
     static TEST_INPUT: [&str; 12] = [
@@ -270,4 +364,16 @@ mod tests {
         assert_eq!(10, co2_scrubber_rating);
         assert_eq!(230, oxygen_generator_rating * co2_scrubber_rating);
     }
+
+    #[test]
+    fn test_solver() {
+        let data = Puzz3
+            .parse(&TEST_INPUT.join("\n"))
+            .expect("Could not parse data");
+        let power = Puzz3.part_one(&data);
+        assert_eq!((22, 9), (power.gamma_value, power.epsilon_value));
+        let ratings = Puzz3.part_two(&data);
+        assert_eq!(23, ratings.oxygen_generator_rating);
+        assert_eq!(10, ratings.co2_scrubber_rating);
+    }
 }
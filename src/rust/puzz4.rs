@@ -1,36 +1,62 @@
+//! Day 4: Giant Squid.
+
 use std::collections::HashSet;
-use std::error::Error;
-use std::path::Path;
+use std::fmt;
+
+use crate::error::AocError;
+use crate::input;
+use crate::solver::{Answer, Solver};
 
-static INPUT_PATH: &str = "input/puzz4.csv";
+static INPUT_FILE: &str = "puzz4.csv";
 
-fn get_input(path: &Path) -> Result<(Vec<u32>, Vec<BingoBoard>), Box<dyn Error>> {
-    let raw_input = std::fs::read_to_string(path)?;
-    let (raw_numbers, raw_boards) = raw_input.split_once('\n').unwrap();
-    let mut numbers: Vec<u32> = Vec::new();
-    for raw_num in raw_numbers.split(',') {
-        numbers.push(raw_num.parse::<u32>()?);
+/// Parse the drawn numbers followed by blank-line-separated 5x5 boards.
+pub fn parse_input(raw_input: &str) -> Result<(Vec<u32>, Vec<BingoBoard>), AocError> {
+    let blocks = input::blocks(raw_input);
+    let (header, raw_boards) = blocks
+        .split_first()
+        .ok_or_else(|| AocError::invalid("Empty input"))?;
+    let numbers = header[0].comma_list::<u32>()?;
+    if let Some(line) = header.get(1) {
+        return Err(line.error(line.text, "Expected a blank line after the drawn numbers"));
+    }
+    if raw_boards.is_empty() {
+        return Err(AocError::invalid(
+            "Expected bingo boards after the drawn numbers",
+        ));
+    }
+    let mut boards = Vec::with_capacity(raw_boards.len());
+    for board in raw_boards {
+        let mut board_elems: Vec<u32> = Vec::with_capacity(25);
+        for line in board {
+            let row = line.whitespace_list::<u32>()?;
+            if row.len() != 5 {
+                return Err(line.error(
+                    line.text,
+                    format!("Expected 5 numbers in a board row, found {}", row.len()),
+                ));
+            }
+            board_elems.extend(row);
+        }
+        if board_elems.len() != 25 {
+            let line = board[0];
+            return Err(line.error(
+                line.text,
+                format!("Expected a 5x5 board, found {} rows", board.len()),
+            ));
+        }
+        boards.push(BingoBoard::initialize_from_row_major_1d_array(&board_elems));
     }
-    let board_elems = raw_boards
-        .split('\n')
-        .filter(|s| !s.is_empty())
-        .flat_map(|s| s.split(' ').filter(|s| !s.is_empty()))
-        .map(|v| v.parse::<u32>().unwrap())
-        .collect::<Vec<u32>>();
-    let boards = board_elems
-        .chunks_exact(25)
-        .map(BingoBoard::initialize_from_row_major_1d_array)
-        .collect::<Vec<BingoBoard>>();
     Ok((numbers, boards))
 }
 
+/// A 5x5 board of numbers with their marked state, stored row-major.
 #[derive(Debug, Clone, Default)]
-struct BingoBoard {
+pub struct BingoBoard {
     data: [(u32, bool); 25],
 }
 
 impl BingoBoard {
-    fn initialize_from_2d_array(arr: &[[u32; 5]; 5]) -> Self {
+    pub fn initialize_from_2d_array(arr: &[[u32; 5]; 5]) -> Self {
         let mut board = BingoBoard::default();
         let mut bidx = 0;
         for row in arr.iter().take(5) {
@@ -41,7 +67,7 @@ impl BingoBoard {
         }
         board
     }
-    fn initialize_from_row_major_1d_array(arr: &[u32]) -> Self {
+    pub fn initialize_from_row_major_1d_array(arr: &[u32]) -> Self {
         assert!(arr.len() == 25);
         let mut board = BingoBoard::default();
         for (&elem, board_elem) in arr.iter().zip(board.data.iter_mut()) {
@@ -52,14 +78,14 @@ impl BingoBoard {
 }
 
 impl BingoBoard {
-    fn mark_number(&mut self, num: u32) {
+    pub fn mark_number(&mut self, num: u32) {
         for &mut (v, ref mut mark) in self.data.iter_mut() {
             if v == num {
                 *mark = true;
             }
         }
     }
-    fn clear_marks(&mut self) {
+    pub fn clear_marks(&mut self) {
         for &mut (_, ref mut mark) in self.data.iter_mut() {
             *mark = false;
         }
@@ -90,7 +116,7 @@ impl BingoBoard {
         }
         None
     }
-    fn check_for_bingo(&self) -> bool {
+    pub fn check_for_bingo(&self) -> bool {
         if self.check_for_bingo_row().is_some() {
             return true;
         }
@@ -101,15 +127,33 @@ impl BingoBoard {
     }
 }
 
+/// Which board won on which draw; its `Display` is the winning score.
 #[derive(Debug, Copy, Clone)]
-struct BingoWinningState {
-    board_idx: usize,
-    num_idx: usize,
-    number: u32,
-    score: u32,
+pub struct BingoWinningState {
+    pub board_idx: usize,
+    pub num_idx: usize,
+    pub number: u32,
+    pub score: u32,
 }
 
-fn play_bingo(boards: &mut [BingoBoard], numbers: &[u32]) -> BingoWinningState {
+impl fmt::Display for BingoWinningState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.score)
+    }
+}
+
+impl Answer for BingoWinningState {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("board_idx", self.board_idx.to_string()),
+            ("num_idx", self.num_idx.to_string()),
+            ("number", self.number.to_string()),
+        ]
+    }
+}
+
+/// Play until the first board wins.
+pub fn play_bingo(boards: &mut [BingoBoard], numbers: &[u32]) -> BingoWinningState {
     for board in boards.iter_mut() {
         board.clear_marks();
     }
@@ -141,7 +185,8 @@ fn play_bingo(boards: &mut [BingoBoard], numbers: &[u32]) -> BingoWinningState {
     }
 }
 
-fn play_bingo_to_last_winning_board(
+/// Play until every board has won, reporting the last one.
+pub fn play_bingo_to_last_winning_board(
     boards: &mut [BingoBoard],
     numbers: &[u32],
 ) -> BingoWinningState {
@@ -185,23 +230,41 @@ fn play_bingo_to_last_winning_board(
     }
 }
 
-pub(crate) fn puzz4() {
-    let (numbers, mut boards) =
-        get_input(Path::new(INPUT_PATH)).expect("Could not parse input data");
-    let winning_stats = play_bingo(&mut boards, numbers.as_slice());
-    println!(
-        "Part One: Winning score: {}, from the {}-th board",
-        winning_stats.score, winning_stats.board_idx
-    );
-    let last_winning_stats = play_bingo_to_last_winning_board(&mut boards, numbers.as_slice());
-    println!(
-        "Part Two: Last winning score: {}, from the {}-th board",
-        last_winning_stats.score, last_winning_stats.board_idx
-    );
+/// [`Solver`] for day 4.
+pub struct Puzz4;
+
+impl Solver for Puzz4 {
+    const DAY: usize = 4;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = (Vec<u32>, Vec<BingoBoard>);
+    type PartOne = BingoWinningState;
+    type PartTwo = BingoWinningState;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, (numbers, boards): &Self::Input) -> Self::PartOne {
+        play_bingo(&mut boards.clone(), numbers.as_slice())
+    }
+    fn part_two(&self, (numbers, boards): &Self::Input) -> Self::PartTwo {
+        play_bingo_to_last_winning_board(&mut boards.clone(), numbers.as_slice())
+    }
+    fn describe_part_one(&self, winning_stats: &Self::PartOne) -> String {
+        format!(
+            "Part One: Winning score: {}, from the {}-th board",
+            winning_stats.score, winning_stats.board_idx
+        )
+    }
+    fn describe_part_two(&self, last_winning_stats: &Self::PartTwo) -> String {
+        format!(
+            "Part Two: Last winning score: {}, from the {}-th board",
+            last_winning_stats.score, last_winning_stats.board_idx
+        )
+    }
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
     static TEST_NUMS: [u32; 27] = [
@@ -236,7 +299,7 @@ mod tests {
     fn test_part_one() {
         let mut boards: Vec<BingoBoard> = TEST_BOARDS
             .iter()
-            .map(|b| BingoBoard::initialize_from_2d_array(b))
+            .map(BingoBoard::initialize_from_2d_array)
             .collect();
         let winning_stats = play_bingo(&mut boards, TEST_NUMS.as_slice());
         assert_eq!(2, winning_stats.board_idx);
@@ -249,7 +312,7 @@ mod tests {
     fn test_part_two() {
         let mut boards: Vec<BingoBoard> = TEST_BOARDS
             .iter()
-            .map(|b| BingoBoard::initialize_from_2d_array(b))
+            .map(BingoBoard::initialize_from_2d_array)
             .collect();
         let last_winner_stats = play_bingo_to_last_winning_board(&mut boards, TEST_NUMS.as_slice());
         assert_eq!(1, last_winner_stats.board_idx);
@@ -257,4 +320,21 @@ mod tests {
         assert_eq!(13, last_winner_stats.number);
         assert_eq!(1924, last_winner_stats.score);
     }
+
+    #[test]
+    fn test_malformed_input() {
+        assert!(parse_input("7,4,9").is_err());
+        match parse_input("7,4,9\n\n22 13 17 11  0\n 8  2 23  4\n") {
+            Err(AocError::Parse { line, .. }) => assert_eq!(4, line),
+            other => panic!("Unexpected result {:?}", other),
+        }
+        match parse_input("7,4,9\r\n\r\n22 13 17 11  0\r\n") {
+            Err(AocError::Parse { line, .. }) => assert_eq!(3, line),
+            other => panic!("Unexpected result {:?}", other),
+        }
+        assert!(matches!(
+            parse_input("7,4,9\n\n"),
+            Err(AocError::InvalidData(_))
+        ));
+    }
 }
@@ -1,48 +1,65 @@
-use std::error::Error;
-use std::path::Path;
-
-static INPUT_PATH: &str = "input/puzz5.txt";
-
-fn get_horizontal_or_vertical_input(path: &Path) -> Result<Vec<Vent>, Box<dyn Error>> {
-    let raw_input = std::fs::read_to_string(path)?;
-    Ok(raw_input
-        .lines()
-        .map(parse_horizontal_or_vertical_vent)
-        .flatten()
-        .collect::<Vec<Vent>>())
-}
+//! Day 5: Hydrothermal Venture.
 
-fn parse_horizontal_or_vertical_vent(line: &str) -> Option<Vent> {
-    let coord_split = line
-        .split("->")
-        .map(|s| s.trim().split(',').map(|s| s.parse::<usize>().unwrap()))
-        .flatten()
-        .collect::<Vec<usize>>();
-    assert_eq!(4, coord_split.len());
-    let start = Point::new(coord_split[0], coord_split[1]);
-    let stop = Point::new(coord_split[2], coord_split[3]);
-    if start.x == stop.x || start.y == stop.y {
-        Some(Vent::new(start, stop))
-    } else {
-        None
-    }
-}
+use crate::error::AocError;
+use crate::input::{self, Line};
+use crate::solver::Solver;
+
+static INPUT_FILE: &str = "puzz5.txt";
+
+/// Width and height of the ocean floor grid the vents are drawn on.
+pub const GRID_SIZE: usize = 1_000;
 
-fn get_all_input(path: &Path) -> Result<Vec<Vent>, Box<dyn Error>> {
-    let raw_input = std::fs::read_to_string(path)?;
-    Ok(raw_input.lines().map(parse_vent).collect::<Vec<Vent>>())
+/// Parse one `x1,y1 -> x2,y2` vent per line.
+pub fn parse_input(raw_input: &str) -> Result<Vec<Vent>, AocError> {
+    input::parse_lines(raw_input, |line| {
+        parse_vent(line.text).map_err(|e| e.at_line(line.number))
+    })
 }
 
-fn parse_vent(line: &str) -> Vent {
-    let coord_split = line
+/// Parse a single `x1,y1 -> x2,y2` vent.
+///
+/// Every coordinate must fit on the [`GRID_SIZE`] grid and the vent must be
+/// horizontal, vertical or at 45 degrees.
+pub fn parse_vent(line: &str) -> Result<Vent, AocError> {
+    let line = Line {
+        number: 1,
+        text: line,
+    };
+    let tokens = line
+        .text
         .split("->")
-        .map(|s| s.trim().split(',').map(|s| s.parse::<usize>().unwrap()))
-        .flatten()
-        .collect::<Vec<usize>>();
-    assert_eq!(4, coord_split.len());
+        .flat_map(|s| s.trim().split(','))
+        .collect::<Vec<&str>>();
+    let coord_split = tokens
+        .iter()
+        .map(|token| line.parse_token::<usize>(token))
+        .collect::<Result<Vec<usize>, AocError>>()?;
+    if coord_split.len() != 4 {
+        return Err(line.error(
+            line.text,
+            format!("Expected `x1,y1 -> x2,y2`, found {:?}", line.text),
+        ));
+    }
+    if let Some((token, coord)) = tokens
+        .iter()
+        .zip(&coord_split)
+        .find(|(_, &c)| c >= GRID_SIZE)
+    {
+        return Err(line.error(
+            token,
+            format!(
+                "Coordinate {} is off the {}x{} grid",
+                coord, GRID_SIZE, GRID_SIZE
+            ),
+        ));
+    }
     let start = Point::new(coord_split[0], coord_split[1]);
     let stop = Point::new(coord_split[2], coord_split[3]);
-    Vent::new(start, stop)
+    let vent = Vent::new(start, stop);
+    if !vent.is_horizontal_or_vertical() && start.x.abs_diff(stop.x) != start.y.abs_diff(stop.y) {
+        return Err(line.error(line.text, "Diagonal vents must be at 45 degrees"));
+    }
+    Ok(vent)
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -51,29 +68,35 @@ enum Direction {
     Neg,
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Point {
-    x: usize,
-    y: usize,
+/// A point on a grid with non-negative coordinates.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
 }
 
 impl Point {
-    fn new(x: usize, y: usize) -> Self {
+    pub fn new(x: usize, y: usize) -> Self {
         Self { x, y }
     }
 }
 
+/// A line of vents between two points, inclusive.
 #[derive(Debug, Copy, Clone)]
-struct Vent {
-    start: Point,
-    stop: Point,
+pub struct Vent {
+    pub start: Point,
+    pub stop: Point,
 }
 
 impl Vent {
-    fn new(start: Point, stop: Point) -> Self {
+    pub fn new(start: Point, stop: Point) -> Self {
         Self { start, stop }
     }
-    fn get_all_points(&self) -> Vec<Point> {
+    pub fn is_horizontal_or_vertical(&self) -> bool {
+        self.start.x == self.stop.x || self.start.y == self.stop.y
+    }
+    /// Every point covered by the vent; diagonals are always at 45 degrees.
+    pub fn get_all_points(&self) -> Vec<Point> {
         let mut vec = Vec::new();
         if self.start.x == self.stop.x {
             let (y_start, y_stop) = if self.stop.y > self.start.y {
@@ -142,24 +165,31 @@ impl Vent {
     }
 }
 
+/// Dense `N`x`N` count of how many vents cover each point.
 #[derive(Debug, Clone)]
-struct Grid<const N: usize> {
+pub struct Grid<const N: usize> {
     grid: [[u8; N]; N],
 }
 
+impl<const N: usize> Default for Grid<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<const N: usize> Grid<N> {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             grid: [[0u8; N]; N],
         }
     }
-    fn mark_vent(&mut self, vent: &Vent) {
+    pub fn mark_vent(&mut self, vent: &Vent) {
         let points = vent.get_all_points();
         for point in points {
             self.grid[point.x][point.y] += 1;
         }
     }
-    fn count_marks_ge(&self, mark_number: u8) -> usize {
+    pub fn count_marks_ge(&self, mark_number: u8) -> usize {
         let mut count = 0;
         for row in self.grid.iter().take(N) {
             for &v in row.iter().take(N) {
@@ -172,28 +202,47 @@ impl<const N: usize> Grid<N> {
     }
 }
 
-pub(crate) fn puzz5() {
-    let vents_part_one =
-        get_horizontal_or_vertical_input(Path::new(INPUT_PATH)).expect("Could not parse input");
-    let mut grid = Grid::<1_000>::new();
-    for vent in vents_part_one.iter() {
+/// Number of points covered by at least two vents on an `N`x`N` grid.
+pub fn count_overlaps<'a, const N: usize>(vents: impl Iterator<Item = &'a Vent>) -> usize {
+    let mut grid = Grid::<N>::new();
+    for vent in vents {
         grid.mark_vent(vent);
     }
-    let two_line_overlap_count = grid.count_marks_ge(2);
-    println!(
-        "Part One: {} points have an overlap from two or more lines",
-        two_line_overlap_count
-    );
-    let vents_part_two = get_all_input(Path::new(INPUT_PATH)).expect("Could not parse input");
-    let mut grid = Grid::<1_000>::new();
-    for vent in vents_part_two.iter() {
-        grid.mark_vent(vent);
+    grid.count_marks_ge(2)
+}
+
+/// [`Solver`] for day 5.
+pub struct Puzz5;
+
+impl Solver for Puzz5 {
+    const DAY: usize = 5;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Vec<Vent>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, vents: &Self::Input) -> Self::PartOne {
+        count_overlaps::<GRID_SIZE>(vents.iter().filter(|v| v.is_horizontal_or_vertical()))
+    }
+    fn part_two(&self, vents: &Self::Input) -> Self::PartTwo {
+        count_overlaps::<GRID_SIZE>(vents.iter())
+    }
+    fn describe_part_one(&self, two_line_overlap_count: &Self::PartOne) -> String {
+        format!(
+            "Part One: {} points have an overlap from two or more lines",
+            two_line_overlap_count
+        )
+    }
+    fn describe_part_two(&self, two_line_overlap_count: &Self::PartTwo) -> String {
+        format!(
+            "Part Two: {} points have an overlap from two or more lines",
+            two_line_overlap_count
+        )
     }
-    let two_line_overlap_count = grid.count_marks_ge(2);
-    println!(
-        "Part Two: {} points have an overlap from two or more lines",
-        two_line_overlap_count
-    );
 }
 
 #[cfg(test)]
@@ -214,8 +263,8 @@ mod tests {
     fn test_part_one() {
         let vents = TEST_INPUT
             .lines()
-            .map(parse_horizontal_or_vertical_vent)
-            .flatten()
+            .map(|line| parse_vent(line).unwrap())
+            .filter(Vent::is_horizontal_or_vertical)
             .collect::<Vec<Vent>>();
         let mut grid = Grid::<10>::new();
         for vent in vents.iter() {
@@ -227,7 +276,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let vents = TEST_INPUT.lines().map(parse_vent).collect::<Vec<Vent>>();
+        let vents = parse_input(TEST_INPUT).unwrap();
         let mut grid = Grid::<10>::new();
         for vent in vents.iter() {
             grid.mark_vent(vent);
@@ -235,4 +284,29 @@ mod tests {
         let two_line_overlap_count = grid.count_marks_ge(2);
         assert_eq!(12, two_line_overlap_count);
     }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((2, 8)),
+            parse_input("0,9 -> 5,9\n8,0 -> x,8")
+                .unwrap_err()
+                .location()
+        );
+        assert!(parse_input("0,9 -> 5").is_err());
+        assert_eq!(
+            Some((2, 1)),
+            parse_input("0,9 -> 5,9\n1000,0 -> 1000,5")
+                .unwrap_err()
+                .location()
+        );
+        assert_eq!(
+            Some((1, 12)),
+            parse_input("0,999 -> 0,1000").unwrap_err().location()
+        );
+        assert_eq!(
+            Some((1, 1)),
+            parse_input("2,1 -> 0,0").unwrap_err().location()
+        );
+    }
 }
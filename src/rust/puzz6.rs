@@ -1,16 +1,25 @@
-use std::error::Error;
-use std::path::Path;
+//! Day 6: Lanternfish.
 
-static INPUT_PATH: &str = "input/puzz6.txt";
+use crate::error::AocError;
+use crate::input;
+use crate::solver::Solver;
 
-fn get_input(path: &Path) -> Result<Vec<u8>, Box<dyn Error>> {
-    Ok(std::fs::read_to_string(path)?
+static INPUT_FILE: &str = "puzz6.txt";
+
+/// Parse the comma-separated list of fish timers, each from 0 to 8.
+pub fn parse_input(raw: &str) -> Result<Vec<u8>, AocError> {
+    let line = input::single_line(raw)?;
+    line.text
         .split(',')
-        .map(|s| s.parse::<u8>().unwrap())
-        .collect::<Vec<u8>>())
+        .map(|token| match line.parse_token::<u8>(token)? {
+            timer @ 0..=8 => Ok(timer),
+            timer => Err(line.error(token, format!("Fish timer {} is outside 0..=8", timer))),
+        })
+        .collect()
 }
 
-fn sim_day_simple(fish_list: &mut Vec<u8>) {
+/// Advance one day, tracking every fish individually.
+pub fn sim_day_simple(fish_list: &mut Vec<u8>) {
     let mut new_fish: Vec<u8> = Vec::new();
     for f in fish_list.iter_mut() {
         if *f > 0 {
@@ -23,35 +32,57 @@ fn sim_day_simple(fish_list: &mut Vec<u8>) {
     fish_list.append(&mut new_fish);
 }
 
-fn sim_day(breeder_state: &mut [usize; 9]) {
+/// Advance one day, tracking the number of fish per timer value.
+pub fn sim_day(breeder_state: &mut [usize; 9]) {
     let breeders = breeder_state[0];
     breeder_state.copy_within(1..9, 0);
     breeder_state[6] += breeders;
     breeder_state[8] = breeders;
 }
 
-pub(crate) fn puzz6() {
-    let mut fish_list: Vec<u8> = get_input(Path::new(&INPUT_PATH)).expect("Could not parse input");
-    for _ in 0..80 {
-        sim_day_simple(&mut fish_list);
+/// [`Solver`] for day 6.
+pub struct Puzz6;
+
+impl Solver for Puzz6 {
+    const DAY: usize = 6;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Vec<u8>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
     }
-    println!(
-        "Part One: There will be {} lantern fish after 80 days",
+    fn part_one(&self, fish_list: &Self::Input) -> Self::PartOne {
+        let mut fish_list = fish_list.clone();
+        for _ in 0..80 {
+            sim_day_simple(&mut fish_list);
+        }
         fish_list.len()
-    );
-    let mut breeder_state: [usize; 9] = [0; 9];
-    let fish_list: Vec<u8> = get_input(Path::new(&INPUT_PATH)).expect("Could not parse input");
-    for fish_state in fish_list {
-        breeder_state[fish_state as usize] += 1;
     }
-    for _ in 0..256 {
-        sim_day(&mut breeder_state);
+    fn part_two(&self, fish_list: &Self::Input) -> Self::PartTwo {
+        let mut breeder_state: [usize; 9] = [0; 9];
+        for &fish_state in fish_list {
+            breeder_state[fish_state as usize] += 1;
+        }
+        for _ in 0..256 {
+            sim_day(&mut breeder_state);
+        }
+        breeder_state.iter().sum::<usize>()
+    }
+    fn describe_part_one(&self, num_fish: &Self::PartOne) -> String {
+        format!(
+            "Part One: There will be {} lantern fish after 80 days",
+            num_fish
+        )
+    }
+    fn describe_part_two(&self, num_fish: &Self::PartTwo) -> String {
+        format!(
+            "Part Two: There will be {} lantern fish after 256 days",
+            num_fish
+        )
     }
-    let num_fish = breeder_state.iter().sum::<usize>();
-    println!(
-        "Part Two: There will be {} lantern fish after 256 days",
-        num_fish
-    )
 }
 
 #[cfg(test)]
@@ -91,4 +122,13 @@ mod tests {
         let num_fish = breeder_state.iter().sum::<usize>();
         assert_eq!(26984457539, num_fish);
     }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((2, 5)),
+            parse_input("\n3,4,9,1").unwrap_err().location()
+        );
+        assert_eq!(Some((1, 3)), parse_input("3,x").unwrap_err().location());
+    }
 }
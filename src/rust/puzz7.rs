@@ -1,22 +1,44 @@
-use std::error::Error;
-use std::path::Path;
+//! Day 7: The Treachery of Whales.
 
-static INPUT_PATH: &str = "input/puzz7.txt";
+use std::fmt;
 
-fn get_input(path: &Path) -> Result<Vec<isize>, Box<dyn Error>> {
-    Ok(std::fs::read_to_string(path)?
-        .split(',')
-        .map(|s| s.parse::<isize>().unwrap())
-        .collect::<Vec<isize>>())
+use crate::error::AocError;
+use crate::input;
+use crate::solver::{Answer, Solver};
+
+static INPUT_FILE: &str = "puzz7.txt";
+
+/// Parse the comma-separated list of crab positions.
+pub fn parse_input(raw: &str) -> Result<Vec<isize>, AocError> {
+    input::comma_list(raw)
+}
+
+/// Where the crabs align and what it costs; its `Display` is the fuel cost.
+#[derive(Debug, Copy, Clone)]
+pub struct Alignment {
+    pub position: isize,
+    pub fuel_cost: isize,
+}
+
+impl fmt::Display for Alignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.fuel_cost)
+    }
+}
+
+impl Answer for Alignment {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![("position", self.position.to_string())]
+    }
 }
 
-pub(crate) fn puzz7() {
-    let mut crab_hpos: Vec<isize> =
-        get_input(Path::new(&INPUT_PATH)).expect("Could not parse input");
+/// Cheapest alignment when each step costs one unit of fuel.
+pub fn align_at_median(crab_hpos: &[isize]) -> Alignment {
+    let mut crab_hpos = crab_hpos.to_vec();
     crab_hpos.sort_unstable();
     let num_crabs = crab_hpos.len();
-    let median_pos = if num_crabs % 2 == 0 {
-        ((crab_hpos[num_crabs / 2 - 1] + crab_hpos[(num_crabs / 2)]) as f64 / 2.0_f64).floor()
+    let median_pos = if num_crabs.is_multiple_of(2) {
+        ((crab_hpos[num_crabs / 2 - 1] + crab_hpos[num_crabs / 2]) as f64 / 2.0_f64).floor()
             as isize
     } else {
         crab_hpos[(num_crabs - 1) / 2]
@@ -25,25 +47,29 @@ pub(crate) fn puzz7() {
         .iter()
         .map(|&p| (p - median_pos).abs())
         .sum::<isize>();
-    println!(
-        "Part One: Optimal fuel spend {} aligning at horizontal position {}",
-        fuel_cost, median_pos
-    );
+    Alignment {
+        position: median_pos,
+        fuel_cost,
+    }
+}
 
+/// Cheapest alignment when each further step costs one more unit of fuel.
+pub fn align_at_mean(crab_hpos: &[isize]) -> Alignment {
+    let num_crabs = crab_hpos.len();
     let mean_pos1 = (crab_hpos.iter().sum::<isize>() as f64 / num_crabs as f64).floor() as isize;
     let mean_pos2 = (crab_hpos.iter().sum::<isize>() as f64 / num_crabs as f64).ceil() as isize;
     let fuel_cost1 = crab_hpos
         .iter()
         .map(|&p| {
             let num_moves = (p - mean_pos1).abs();
-            (1..=num_moves).into_iter().sum::<isize>()
+            (1..=num_moves).sum::<isize>()
         })
         .sum::<isize>();
     let fuel_cost2 = crab_hpos
         .iter()
         .map(|&p| {
             let num_moves = (p - mean_pos2).abs();
-            (1..=num_moves).into_iter().sum::<isize>()
+            (1..=num_moves).sum::<isize>()
         })
         .sum::<isize>();
     let (mean_pos, fuel_cost) = if fuel_cost1 < fuel_cost2 {
@@ -51,10 +77,44 @@ pub(crate) fn puzz7() {
     } else {
         (mean_pos2, fuel_cost2)
     };
-    println!(
-        "Part Two: Optimal fuel spend {} aligning at horizontal position {}",
-        fuel_cost, mean_pos
-    );
+    Alignment {
+        position: mean_pos,
+        fuel_cost,
+    }
+}
+
+/// [`Solver`] for day 7.
+pub struct Puzz7;
+
+impl Solver for Puzz7 {
+    const DAY: usize = 7;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Vec<isize>;
+    type PartOne = Alignment;
+    type PartTwo = Alignment;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, crab_hpos: &Self::Input) -> Self::PartOne {
+        align_at_median(crab_hpos)
+    }
+    fn part_two(&self, crab_hpos: &Self::Input) -> Self::PartTwo {
+        align_at_mean(crab_hpos)
+    }
+    fn describe_part_one(&self, alignment: &Self::PartOne) -> String {
+        format!(
+            "Part One: Optimal fuel spend {} aligning at horizontal position {}",
+            alignment.fuel_cost, alignment.position
+        )
+    }
+    fn describe_part_two(&self, alignment: &Self::PartTwo) -> String {
+        format!(
+            "Part Two: Optimal fuel spend {} aligning at horizontal position {}",
+            alignment.fuel_cost, alignment.position
+        )
+    }
 }
 
 #[cfg(test)]
@@ -64,50 +124,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let mut crab_hpos = TEST_INPUT
-            .split(',')
-            .map(|s| s.parse::<isize>().unwrap())
-            .collect::<Vec<isize>>();
-        crab_hpos.sort_unstable();
-        let num_crabs = crab_hpos.len();
-        let median_pos = if num_crabs % 2 == 0 {
-            ((crab_hpos[num_crabs / 2 - 1] + crab_hpos[(num_crabs / 2)]) as f64 / 2.0_f64).floor()
-                as isize
-        } else {
-            crab_hpos[(num_crabs - 1) / 2]
-        };
-        let fuel_cost = crab_hpos
-            .iter()
-            .map(|&p| (p - median_pos).abs())
-            .sum::<isize>();
-        assert_eq!(37, fuel_cost);
+        let crab_hpos = parse_input(TEST_INPUT).unwrap();
+        let alignment = align_at_median(&crab_hpos);
+        assert_eq!(2, alignment.position);
+        assert_eq!(37, alignment.fuel_cost);
     }
 
     #[test]
     fn test_part_two() {
-        let crab_hpos = TEST_INPUT
-            .split(',')
-            .map(|s| s.parse::<isize>().unwrap())
-            .collect::<Vec<isize>>();
-        let num_crabs = crab_hpos.len();
-        let mean_pos1 =
-            (crab_hpos.iter().sum::<isize>() as f64 / num_crabs as f64).floor() as isize;
-        let mean_pos2 = (crab_hpos.iter().sum::<isize>() as f64 / num_crabs as f64).ceil() as isize;
-        let fuel_cost1 = crab_hpos
-            .iter()
-            .map(|&p| {
-                let num_moves = (p - mean_pos1).abs();
-                (1..=num_moves).into_iter().sum::<isize>()
-            })
-            .sum::<isize>();
-        let fuel_cost2 = crab_hpos
-            .iter()
-            .map(|&p| {
-                let num_moves = (p - mean_pos2).abs();
-                (1..=num_moves).into_iter().sum::<isize>()
-            })
-            .sum::<isize>();
-        let fuel_cost = [fuel_cost1, fuel_cost2].into_iter().min().unwrap();
-        assert_eq!(168, fuel_cost);
+        let crab_hpos = parse_input(TEST_INPUT).unwrap();
+        let alignment = align_at_mean(&crab_hpos);
+        assert_eq!(5, alignment.position);
+        assert_eq!(168, alignment.fuel_cost);
     }
 }
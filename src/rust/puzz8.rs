@@ -0,0 +1,227 @@
+//! Day 8: Seven Segment Search.
+
+use crate::error::AocError;
+use crate::input::{self, Line};
+use crate::solver::Solver;
+
+static INPUT_FILE: &str = "puzz8.txt";
+
+/// Segments lit for each digit, as bitmasks with segment `a` in bit 0.
+const DIGIT_SEGMENTS: [Wires; 10] = [
+    0b1110111, 0b0100100, 0b1011101, 0b1101101, 0b0101110, 0b1101011, 0b1111011, 0b0100101,
+    0b1111111, 0b1101111,
+];
+
+/// A set of wires `a` to `g`, as a bitmask with wire `a` in bit 0.
+pub type Wires = u8;
+
+/// One line of notes: the ten unique signal patterns and the four-digit output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Note {
+    pub patterns: [Wires; 10],
+    pub output: [Wires; 4],
+}
+
+fn parse_wires(line: &Line, token: &str) -> Result<Wires, AocError> {
+    let mut wires: Wires = 0;
+    for (idx, c) in token.char_indices() {
+        match c {
+            'a'..='g' => wires |= 1 << (c as u8 - b'a'),
+            _ => return Err(line.error(&token[idx..], format!("Unexpected wire {:?}", c))),
+        }
+    }
+    Ok(wires)
+}
+
+fn parse_group<const N: usize>(line: &Line, group: &str) -> Result<[Wires; N], AocError> {
+    let tokens = group.split_whitespace().collect::<Vec<&str>>();
+    if tokens.len() != N {
+        return Err(line.error(
+            group.trim_start(),
+            format!("Expected {} patterns, found {}", N, tokens.len()),
+        ));
+    }
+    let mut wires = [0; N];
+    for (w, token) in wires.iter_mut().zip(tokens) {
+        *w = parse_wires(line, token)?;
+    }
+    Ok(wires)
+}
+
+/// Parse a single `patterns | output` note.
+pub fn parse_note(line: &Line) -> Result<Note, AocError> {
+    let (patterns, output) = line
+        .text
+        .split_once('|')
+        .ok_or_else(|| line.error(line.text, "Expected `patterns | output`"))?;
+    let note = Note {
+        patterns: parse_group(line, patterns)?,
+        output: parse_group(line, output)?,
+    };
+    if decode_output(&note).is_none() {
+        return Err(line.error(line.text, "Could not deduce the wiring of this display"));
+    }
+    Ok(note)
+}
+
+/// Parse one note per line, checking that every display can be decoded.
+pub fn parse_input(raw: &str) -> Result<Vec<Note>, AocError> {
+    input::parse_lines(raw, |line| parse_note(&line))
+}
+
+/// Number of output digits that are a 1, 4, 7 or 8, which are the only digits
+/// lighting 2, 4, 3 and 7 segments respectively.
+pub fn count_unique_digits(notes: &[Note]) -> usize {
+    notes
+        .iter()
+        .flat_map(|note| note.output.iter())
+        .filter(|wires| matches!(wires.count_ones(), 2 | 3 | 4 | 7))
+        .count()
+}
+
+/// Which segment each wire drives: `segments[w]` is the segment, as a bit
+/// index, of wire `w`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Wiring {
+    pub segments: [u8; 7],
+}
+
+impl Wiring {
+    /// The digit shown when `wires` are lit, if any.
+    pub fn decode_digit(&self, wires: Wires) -> Option<usize> {
+        let segments = (0..7)
+            .filter(|w| wires & (1 << w) != 0)
+            .fold(0, |acc, w| acc | (1 << self.segments[w]));
+        DIGIT_SEGMENTS.iter().position(|&digit| digit == segments)
+    }
+}
+
+/// Deduce the wiring from the ten unique signal patterns.
+///
+/// Across all ten digits, segments b, e and f are lit 6, 4 and 9 times; a and
+/// c are both lit 8 times but only c is part of the 1, and d and g are both lit
+/// 7 times but only d is part of the 4.
+pub fn deduce_wiring(patterns: &[Wires; 10]) -> Option<Wiring> {
+    let one = *patterns.iter().find(|p| p.count_ones() == 2)?;
+    let four = *patterns.iter().find(|p| p.count_ones() == 4)?;
+    let mut segments = [0; 7];
+    for (wire, segment) in segments.iter_mut().enumerate() {
+        let bit = 1 << wire;
+        let count = patterns.iter().filter(|&&p| p & bit != 0).count();
+        *segment = match (count, one & bit != 0, four & bit != 0) {
+            (8, false, _) => 0,
+            (6, _, _) => 1,
+            (8, true, _) => 2,
+            (7, _, true) => 3,
+            (4, _, _) => 4,
+            (9, _, _) => 5,
+            (7, _, false) => 6,
+            _ => return None,
+        };
+    }
+    let wiring = Wiring { segments };
+    let mut seen = [false; 10];
+    for &pattern in patterns {
+        let digit = wiring.decode_digit(pattern)?;
+        if std::mem::replace(&mut seen[digit], true) {
+            return None;
+        }
+    }
+    Some(wiring)
+}
+
+/// The four-digit value shown on the display described by `note`.
+pub fn decode_output(note: &Note) -> Option<usize> {
+    let wiring = deduce_wiring(&note.patterns)?;
+    note.output.iter().try_fold(0, |acc, &wires| {
+        Some(acc * 10 + wiring.decode_digit(wires)?)
+    })
+}
+
+/// [`Solver`] for day 8.
+pub struct Puzz8;
+
+impl Solver for Puzz8 {
+    const DAY: usize = 8;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Vec<Note>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, notes: &Self::Input) -> Self::PartOne {
+        count_unique_digits(notes)
+    }
+    fn part_two(&self, notes: &Self::Input) -> Self::PartTwo {
+        notes
+            .iter()
+            .map(|note| decode_output(note).expect("Notes are checked while parsing"))
+            .sum()
+    }
+    fn describe_part_one(&self, count: &Self::PartOne) -> String {
+        format!("Part One: Digits 1, 4, 7 or 8 appear {} times", count)
+    }
+    fn describe_part_two(&self, sum: &Self::PartTwo) -> String {
+        format!("Part Two: Sum of the decoded output values: {}", sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    #[test]
+    fn test_part_one() {
+        let notes = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(26, count_unique_digits(&notes));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let notes = parse_input(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+        )
+        .unwrap();
+        let wiring = deduce_wiring(&notes[0].patterns).unwrap();
+        // Wires a-g drive segments c, f, g, a, b, d, e.
+        assert_eq!([2, 5, 6, 0, 1, 3, 4], wiring.segments);
+        assert_eq!(Some(5353), decode_output(&notes[0]));
+        let notes = parse_input(TEST_INPUT).unwrap();
+        let outputs = notes
+            .iter()
+            .map(|note| decode_output(note).unwrap())
+            .collect::<Vec<usize>>();
+        assert_eq!(
+            vec![8394, 9781, 1197, 9361, 4873, 8418, 4548, 1625, 8717, 4315],
+            outputs
+        );
+        assert_eq!(61229, Puzz8.part_two(&notes));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Some((1, 1)),
+            parse_input("ab cd | ef").unwrap_err().location()
+        );
+        let bad_wire = "ab cx ab ab ab ab ab ab ab ab | ab ab ab ab";
+        assert_eq!(Some((1, 5)), parse_input(bad_wire).unwrap_err().location());
+        // Ten patterns that are not the ten digits.
+        let same = "ab ab ab ab ab ab ab ab ab ab | ab ab ab ab";
+        assert_eq!(Some((1, 1)), parse_input(same).unwrap_err().location());
+    }
+}
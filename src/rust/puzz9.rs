@@ -0,0 +1,171 @@
+//! Day 9: Smoke Basin.
+
+use std::fmt;
+
+use crate::error::AocError;
+use crate::input::{self, Grid};
+use crate::solver::{Answer, Solver};
+
+static INPUT_FILE: &str = "puzz9.txt";
+
+/// Parse the height map, one digit per location.
+pub fn parse_input(raw: &str) -> Result<Grid<u8>, AocError> {
+    input::grid(raw, |c| c.to_digit(10).map(|height| height as u8))
+}
+
+/// Locations lower than every orthogonal neighbour, in row-major order.
+pub fn low_points(heights: &Grid<u8>) -> Vec<(usize, usize)> {
+    let mut points = Vec::new();
+    for y in 0..heights.height {
+        for x in 0..heights.width {
+            let height = heights[(x, y)];
+            if heights
+                .neighbours(x, y)
+                .all(|(nx, ny)| heights[(nx, ny)] > height)
+            {
+                points.push((x, y));
+            }
+        }
+    }
+    points
+}
+
+/// Sum of one plus the height of every low point.
+pub fn total_risk_level(heights: &Grid<u8>) -> u32 {
+    low_points(heights)
+        .into_iter()
+        .map(|(x, y)| heights[(x, y)] as u32 + 1)
+        .sum()
+}
+
+/// Number of locations flowing down to the low point at `start`, found by
+/// flood-filling outwards until reaching height 9.
+pub fn basin_size(heights: &Grid<u8>, start: (usize, usize)) -> usize {
+    let mut visited = vec![false; heights.cells.len()];
+    let mut stack = vec![start];
+    visited[start.1 * heights.width + start.0] = true;
+    let mut size = 0;
+    while let Some((x, y)) = stack.pop() {
+        size += 1;
+        for (nx, ny) in heights.neighbours(x, y) {
+            let idx = ny * heights.width + nx;
+            if !visited[idx] && heights[(nx, ny)] < 9 {
+                visited[idx] = true;
+                stack.push((nx, ny));
+            }
+        }
+    }
+    size
+}
+
+/// Sizes of the basins around every low point, in low point order.
+pub fn basin_sizes(heights: &Grid<u8>) -> Vec<usize> {
+    low_points(heights)
+        .into_iter()
+        .map(|point| basin_size(heights, point))
+        .collect()
+}
+
+/// The three largest basins; its `Display` is the product of their sizes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LargestBasins {
+    pub sizes: [usize; 3],
+}
+
+impl LargestBasins {
+    pub fn product(&self) -> usize {
+        self.sizes.iter().product()
+    }
+}
+
+impl fmt::Display for LargestBasins {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.product())
+    }
+}
+
+impl Answer for LargestBasins {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("first", self.sizes[0].to_string()),
+            ("second", self.sizes[1].to_string()),
+            ("third", self.sizes[2].to_string()),
+        ]
+    }
+}
+
+/// The three largest basins, largest first; missing basins count as size 0.
+pub fn largest_basins(heights: &Grid<u8>) -> LargestBasins {
+    let mut sizes = basin_sizes(heights);
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    sizes.resize(3.max(sizes.len()), 0);
+    LargestBasins {
+        sizes: [sizes[0], sizes[1], sizes[2]],
+    }
+}
+
+/// [`Solver`] for day 9.
+pub struct Puzz9;
+
+impl Solver for Puzz9 {
+    const DAY: usize = 9;
+    const INPUT_FILE: &'static str = INPUT_FILE;
+
+    type Input = Grid<u8>;
+    type PartOne = u32;
+    type PartTwo = LargestBasins;
+
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError> {
+        parse_input(raw)
+    }
+    fn part_one(&self, heights: &Self::Input) -> Self::PartOne {
+        total_risk_level(heights)
+    }
+    fn part_two(&self, heights: &Self::Input) -> Self::PartTwo {
+        largest_basins(heights)
+    }
+    fn describe_part_one(&self, risk: &Self::PartOne) -> String {
+        format!("Part One: Sum of the low point risk levels: {}", risk)
+    }
+    fn describe_part_two(&self, basins: &Self::PartTwo) -> String {
+        format!(
+            "Part Two: Product of the three largest basin sizes {:?}: {}",
+            basins.sizes,
+            basins.product()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_INPUT: &str = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+    #[test]
+    fn test_part_one() {
+        let heights = parse_input(TEST_INPUT).unwrap();
+        assert_eq!((10, 5), (heights.width, heights.height));
+        assert_eq!(vec![(1, 0), (9, 0), (2, 2), (6, 4)], low_points(&heights));
+        assert_eq!(15, total_risk_level(&heights));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let heights = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(vec![3, 9, 14, 9], basin_sizes(&heights));
+        let basins = largest_basins(&heights);
+        assert_eq!([14, 9, 9], basins.sizes);
+        assert_eq!(1134, basins.product());
+    }
+
+    #[test]
+    fn test_other_dimensions() {
+        let heights = parse_input("191\n999\n").unwrap();
+        assert_eq!(vec![(0, 0), (2, 0)], low_points(&heights));
+        assert_eq!([1, 1, 0], largest_basins(&heights).sizes);
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use crate::error::AocError;
+use crate::{
+    puzz1, puzz10, puzz11, puzz12, puzz13, puzz14, puzz15, puzz16, puzz17, puzz18, puzz19, puzz2,
+    puzz20, puzz21, puzz22, puzz3, puzz4, puzz5, puzz6, puzz7, puzz8, puzz9,
+};
+
+/// Which parts of a day to solve.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Parts {
+    One,
+    Two,
+    #[default]
+    Both,
+}
+
+impl Parts {
+    pub fn part_one(self) -> bool {
+        matches!(self, Parts::One | Parts::Both)
+    }
+    pub fn part_two(self) -> bool {
+        matches!(self, Parts::Two | Parts::Both)
+    }
+}
+
+/// A typed answer to one part of a day.
+///
+/// `Display` renders the value that gets submitted to the site; `fields`
+/// lists any supporting figures worth reporting alongside it.
+pub trait Answer: Display {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+}
+
+macro_rules! plain_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {})*
+    };
+}
+
+plain_answer!(u32, u64, usize, i32, i64, isize, String);
+
+/// One day of the calendar: how to parse its input and solve both parts.
+///
+/// Answers are typed so that tests can inspect them directly.
+pub trait Solver {
+    /// Day of the month, starting at 1.
+    const DAY: usize;
+    /// File name of the puzzle input within the input directory.
+    const INPUT_FILE: &'static str;
+
+    type Input;
+    type PartOne: Answer;
+    type PartTwo: Answer;
+
+    /// Parse the raw text of the puzzle input.
+    fn parse(&self, raw: &str) -> Result<Self::Input, AocError>;
+    fn part_one(&self, input: &Self::Input) -> Self::PartOne;
+    fn part_two(&self, input: &Self::Input) -> Self::PartTwo;
+
+    /// Human-friendly sentence reporting the part one answer.
+    fn describe_part_one(&self, answer: &Self::PartOne) -> String {
+        format!("Part One: {}", answer)
+    }
+    /// Human-friendly sentence reporting the part two answer.
+    fn describe_part_two(&self, answer: &Self::PartTwo) -> String {
+        format!("Part Two: {}", answer)
+    }
+}
+
+/// Object-safe view of a `Solver`, so that days with different input and
+/// answer types can live side by side in the registry. Puzzles are shared
+/// between threads when days run in parallel.
+pub trait Puzzle: Send + Sync {
+    fn day(&self) -> usize;
+    fn input_file(&self) -> &'static str;
+    /// Parse `raw` and solve the selected parts, timing each stage.
+    fn run(&self, raw: &str, parts: Parts) -> Result<DayReport, AocError>;
+}
+
+/// The answer to one part, with how long it took to compute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartReport {
+    /// 1 or 2.
+    pub part: usize,
+    pub answer: String,
+    /// Supporting figures from [`Answer::fields`].
+    pub fields: Vec<(&'static str, String)>,
+    pub description: String,
+    pub elapsed: Duration,
+}
+
+/// The outcome of running one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayReport {
+    pub day: usize,
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+impl<S: Solver + Send + Sync> Puzzle for S {
+    fn day(&self) -> usize {
+        S::DAY
+    }
+    fn input_file(&self) -> &'static str {
+        S::INPUT_FILE
+    }
+    fn run(&self, raw: &str, parts: Parts) -> Result<DayReport, AocError> {
+        let (input, parse_time) = timed(|| self.parse(raw));
+        let input = input?;
+        let mut report = DayReport {
+            day: S::DAY,
+            parse_time,
+            parts: Vec::new(),
+        };
+        if parts.part_one() {
+            let (answer, elapsed) = timed(|| self.part_one(&input));
+            report.parts.push(PartReport {
+                part: 1,
+                answer: answer.to_string(),
+                fields: answer.fields(),
+                description: self.describe_part_one(&answer),
+                elapsed,
+            });
+        }
+        if parts.part_two() {
+            let (answer, elapsed) = timed(|| self.part_two(&input));
+            report.parts.push(PartReport {
+                part: 2,
+                answer: answer.to_string(),
+                fields: answer.fields(),
+                description: self.describe_part_two(&answer),
+                elapsed,
+            });
+        }
+        Ok(report)
+    }
+}
+
+/// Every implemented day, ordered by day number.
+pub fn registry() -> Vec<Box<dyn Puzzle>> {
+    vec![
+        Box::new(puzz1::Puzz1),
+        Box::new(puzz2::Puzz2),
+        Box::new(puzz3::Puzz3),
+        Box::new(puzz4::Puzz4),
+        Box::new(puzz5::Puzz5),
+        Box::new(puzz6::Puzz6),
+        Box::new(puzz7::Puzz7),
+        Box::new(puzz8::Puzz8),
+        Box::new(puzz9::Puzz9),
+        Box::new(puzz10::Puzz10),
+        Box::new(puzz11::Puzz11),
+        Box::new(puzz12::Puzz12),
+        Box::new(puzz13::Puzz13),
+        Box::new(puzz14::Puzz14),
+        Box::new(puzz15::Puzz15),
+        Box::new(puzz16::Puzz16),
+        Box::new(puzz17::Puzz17),
+        Box::new(puzz18::Puzz18),
+        Box::new(puzz19::Puzz19),
+        Box::new(puzz20::Puzz20),
+        Box::new(puzz21::Puzz21),
+        Box::new(puzz22::Puzz22),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_ordered_by_day() {
+        for (idx, puzzle) in registry().iter().enumerate() {
+            assert_eq!(idx + 1, puzzle.day());
+        }
+    }
+}
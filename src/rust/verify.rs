@@ -0,0 +1,292 @@
+//! Checking fresh answers against an `answers.toml` file of known-good ones.
+//!
+//! The file uses a small subset of TOML: one table per input file, named by
+//! the input's path relative to the answers file, holding the accepted answer
+//! for each part.
+//!
+//! ```toml
+//! ["input/puzz4.csv"]
+//! part1 = "4512"
+//! part2 = 1924
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use aoc_2021_rust::error::{column_of, AocError};
+use aoc_2021_rust::solver::DayReport;
+
+pub(crate) static DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    input: PathBuf,
+    answers: [Option<String>; 2],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct ExpectedAnswers {
+    entries: Vec<Entry>,
+}
+
+fn unquote(raw: &str, line_no: usize, line: &str) -> Result<String, AocError> {
+    let column = column_of(line, raw);
+    let inner = raw
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .ok_or_else(|| {
+            AocError::parse(
+                line_no,
+                column,
+                format!("Expected a quoted string, found {}", raw),
+            )
+        })?;
+    if inner.contains('"') || inner.contains('\\') {
+        return Err(AocError::parse(
+            line_no,
+            column,
+            "Escape sequences are not supported",
+        ));
+    }
+    Ok(String::from(inner))
+}
+
+impl ExpectedAnswers {
+    /// Parse the contents of an answers file; table names are resolved
+    /// relative to `base_dir`.
+    pub(crate) fn parse(raw: &str, base_dir: &Path) -> Result<Self, AocError> {
+        let mut entries: Vec<Entry> = Vec::new();
+        for (lidx, line) in raw.lines().enumerate() {
+            let line_no = lidx + 1;
+            let content = line.trim();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+            if let Some(header) = content.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                let header = header.trim();
+                let name = if header.starts_with('"') {
+                    unquote(header, line_no, line)?
+                } else {
+                    String::from(header)
+                };
+                entries.push(Entry {
+                    input: base_dir.join(name),
+                    answers: [None, None],
+                });
+                continue;
+            }
+            let (key, value) = content.split_once('=').ok_or_else(|| {
+                AocError::parse(
+                    line_no,
+                    1,
+                    format!("Expected `key = value`, found {:?}", content),
+                )
+            })?;
+            let (key, value) = (key.trim(), value.trim());
+            let entry = entries.last_mut().ok_or_else(|| {
+                AocError::parse(
+                    line_no,
+                    1,
+                    "Answers must follow an `[\"input\"]` table header",
+                )
+            })?;
+            let part = match key {
+                "part1" => 0,
+                "part2" => 1,
+                _ => {
+                    return Err(AocError::parse(
+                        line_no,
+                        column_of(line, key),
+                        format!("Unknown key {:?}; expected part1 or part2", key),
+                    ))
+                }
+            };
+            let value = if value.starts_with('"') {
+                unquote(value, line_no, line)?
+            } else if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit() || c == '-') {
+                String::from(value)
+            } else {
+                return Err(AocError::parse(
+                    line_no,
+                    column_of(line, value),
+                    format!("Expected a string or integer, found {:?}", value),
+                ));
+            };
+            entry.answers[part] = Some(value);
+        }
+        Ok(Self { entries })
+    }
+
+    pub(crate) fn load(path: &Path) -> Result<Self, AocError> {
+        let raw = std::fs::read_to_string(path).map_err(|source| AocError::Io {
+            path: Some(path.to_path_buf()),
+            source,
+        })?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        Self::parse(&raw, base_dir).map_err(|e| e.in_file(path))
+    }
+
+    /// The recorded answer for `part` (1 or 2) of the given input file.
+    pub(crate) fn expected(&self, input: &Path, part: usize) -> Option<&str> {
+        let canonical = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+        let input = canonical(input);
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| canonical(&entry.input) == input)
+            .and_then(|entry| entry.answers[part - 1].as_deref())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Check {
+    pub(crate) day: usize,
+    pub(crate) part: usize,
+    pub(crate) answer: String,
+    pub(crate) verdict: Verdict,
+}
+
+pub(crate) fn check_report(
+    expected: &ExpectedAnswers,
+    input: &Path,
+    report: &DayReport,
+) -> Vec<Check> {
+    report
+        .parts
+        .iter()
+        .map(|part| {
+            let verdict = match expected.expected(input, part.part) {
+                Some(answer) if answer == part.answer => Verdict::Pass,
+                Some(answer) => Verdict::Fail {
+                    expected: String::from(answer),
+                },
+                None => Verdict::Missing,
+            };
+            Check {
+                day: report.day,
+                part: part.part,
+                answer: part.answer.clone(),
+                verdict,
+            }
+        })
+        .collect()
+}
+
+/// Render the pass/fail report; returns it with whether every check passed.
+pub(crate) fn render(checks: &[Check]) -> (String, bool) {
+    let mut lines = vec![String::from("Verification")];
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for check in checks {
+        let line = match &check.verdict {
+            Verdict::Pass => {
+                passed += 1;
+                format!(
+                    "PASS day {} part {}: {}",
+                    check.day, check.part, check.answer
+                )
+            }
+            Verdict::Fail { expected } => {
+                failed += 1;
+                format!(
+                    "FAIL day {} part {}: expected {}, got {}",
+                    check.day, check.part, expected, check.answer
+                )
+            }
+            Verdict::Missing => {
+                missing += 1;
+                format!(
+                    "SKIP day {} part {}: no recorded answer for {}",
+                    check.day, check.part, check.answer
+                )
+            }
+        };
+        lines.push(line);
+    }
+    lines.push(format!(
+        "{} passed, {} failed, {} without a recorded answer",
+        passed, failed, missing
+    ));
+    (lines.join("\n"), failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_2021_rust::solver::PartReport;
+
+    use super::*;
+
+    static ANSWERS: &str = "# Known-good answers
+[\"input/puzz4.csv\"]
+part1 = \"4512\"
+part2 = 1924
+
+[alice/puzz4.csv]
+part1 = \"1\"
+";
+
+    fn report() -> DayReport {
+        let part = |part: usize, answer: &str| PartReport {
+            part,
+            answer: String::from(answer),
+            fields: Vec::new(),
+            description: String::new(),
+            elapsed: Duration::ZERO,
+        };
+        DayReport {
+            day: 4,
+            parse_time: Duration::ZERO,
+            parts: vec![part(1, "4512"), part(2, "1925")],
+        }
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = ExpectedAnswers::parse(ANSWERS, Path::new("base")).unwrap();
+        let input = Path::new("base/input/puzz4.csv");
+        assert_eq!(Some("4512"), answers.expected(input, 1));
+        assert_eq!(Some("1924"), answers.expected(input, 2));
+        assert_eq!(
+            Some("1"),
+            answers.expected(Path::new("base/alice/puzz4.csv"), 1)
+        );
+        assert_eq!(None, answers.expected(Path::new("base/alice/puzz4.csv"), 2));
+        assert_eq!(None, answers.expected(Path::new("input/puzz4.csv"), 1));
+    }
+
+    #[test]
+    fn test_malformed_answers() {
+        assert!(ExpectedAnswers::parse("part1 = \"1\"", Path::new("")).is_err());
+        assert!(ExpectedAnswers::parse("[a]\npart3 = \"1\"", Path::new("")).is_err());
+        match ExpectedAnswers::parse("[a]\npart1 = 1x", Path::new("")) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((2, 9), (line, column)),
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_check_report() {
+        let answers = ExpectedAnswers::parse(ANSWERS, Path::new("")).unwrap();
+        let checks = check_report(&answers, Path::new("input/puzz4.csv"), &report());
+        assert_eq!(Verdict::Pass, checks[0].verdict);
+        assert_eq!(
+            Verdict::Fail {
+                expected: String::from("1924")
+            },
+            checks[1].verdict
+        );
+        let (rendered, all_passed) = render(&checks);
+        assert!(!all_passed);
+        assert!(rendered.contains("FAIL day 4 part 2: expected 1924, got 1925"));
+        let checks = check_report(&answers, Path::new("bob/puzz4.csv"), &report());
+        assert_eq!(Verdict::Missing, checks[0].verdict);
+        assert!(render(&checks).1);
+    }
+}
@@ -0,0 +1,85 @@
+use aoc_2021_rust::solver::{registry, Parts, Solver};
+use aoc_2021_rust::{puzz1, puzz2, puzz3, puzz4, puzz5, puzz6, puzz7};
+
+fn answers<S: Solver>(solver: S, raw: &str) -> (String, String) {
+    let input = solver.parse(raw).expect("Could not parse example");
+    (
+        solver.part_one(&input).to_string(),
+        solver.part_two(&input).to_string(),
+    )
+}
+
+static PUZZ4_EXAMPLE: &str =
+    "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+static PUZZ5_EXAMPLE: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+#[test]
+fn test_examples() {
+    let (one, two) = answers(
+        puzz1::Puzz1,
+        "199\n200\n208\n210\n200\n207\n240\n269\n260\n263",
+    );
+    assert_eq!(("7", "5"), (one.as_str(), two.as_str()));
+    let (one, two) = answers(
+        puzz2::Puzz2,
+        "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2",
+    );
+    assert_eq!(("150", "900"), (one.as_str(), two.as_str()));
+    let (one, two) = answers(
+        puzz3::Puzz3,
+        "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010",
+    );
+    assert_eq!(("198", "230"), (one.as_str(), two.as_str()));
+    let (one, two) = answers(puzz4::Puzz4, PUZZ4_EXAMPLE);
+    assert_eq!(("4512", "1924"), (one.as_str(), two.as_str()));
+    let (one, two) = answers(puzz5::Puzz5, PUZZ5_EXAMPLE);
+    assert_eq!(("5", "12"), (one.as_str(), two.as_str()));
+    let (one, two) = answers(puzz6::Puzz6, "3,4,3,1,2");
+    assert_eq!(("5934", "26984457539"), (one.as_str(), two.as_str()));
+    let (one, two) = answers(puzz7::Puzz7, "16,1,2,0,4,2,7,1,2,14");
+    assert_eq!(("37", "168"), (one.as_str(), two.as_str()));
+}
+
+#[test]
+fn test_registry_runs_selected_part() {
+    let puzzles = registry();
+    let report = puzzles[6]
+        .run("16,1,2,0,4,2,7,1,2,14", Parts::Two)
+        .expect("Could not run example");
+    assert_eq!(7, report.day);
+    assert_eq!(1, report.parts.len());
+    assert_eq!(2, report.parts[0].part);
+    assert_eq!("168", report.parts[0].answer);
+    assert_eq!(
+        "Part Two: Optimal fuel spend 168 aligning at horizontal position 5",
+        report.parts[0].description
+    );
+}